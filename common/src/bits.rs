@@ -0,0 +1,526 @@
+//! Sets and maps of booleans at one bit each, for visited sets and obstacle maps that
+//! wouldn't fit on the device at a byte per cell.
+//!
+//! The bits are kept in 32 bit words, the device's native size. The sizes are given in
+//! words since they can't be computed from a number of bits in a const generic yet, use
+//! [`words`] for that: a `BitSet<{ words(1000) }>` holds at least 1000 bits.
+use core::fmt::{self, Display};
+use core::ops::{BitAndAssign, BitOrAssign, ShlAssign, ShrAssign};
+
+use crate::grid::{Error, Pos};
+
+/// The number of bits in a word
+const BITS: usize = u32::BITS as usize;
+/// The longest line a PBM image may have
+const PBM_LINE: usize = 70;
+
+/// The number of words needed to hold `bits` bits
+pub const fn words(bits: usize) -> usize {
+    bits.div_ceil(BITS)
+}
+
+/// A set of the numbers in `0..N * 32`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet<const N: usize> {
+    words: [u32; N],
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> BitSet<N> {
+    pub const fn new() -> Self {
+        Self { words: [0; N] }
+    }
+
+    /// The largest number of bits the set can hold
+    pub const fn capacity(&self) -> usize {
+        N * BITS
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.capacity() && self.words[i / BITS] >> (i % BITS) & 1 == 1
+    }
+
+    /// Sets bit `i`, returns whether it wasn't set before
+    ///
+    /// Panics if `i` is beyond the capacity.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let is_new = !self.contains(i);
+        self.words[i / BITS] |= 1 << (i % BITS);
+        is_new
+    }
+
+    /// Clears bit `i`, returns whether it was set
+    pub fn remove(&mut self, i: usize) -> bool {
+        let was_set = self.contains(i);
+        if was_set {
+            self.words[i / BITS] &= !(1 << (i % BITS));
+        }
+        was_set
+    }
+
+    /// Flips bit `i`, returns whether it's set now
+    ///
+    /// Panics if `i` is beyond the capacity.
+    pub fn toggle(&mut self, i: usize) -> bool {
+        self.words[i / BITS] ^= 1 << (i % BITS);
+        self.contains(i)
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        if value {
+            self.insert(i);
+        } else {
+            self.remove(i);
+        }
+    }
+
+    /// Clears every bit
+    pub fn clear(&mut self) {
+        self.words = [0; N];
+    }
+
+    /// The number of bits set
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// The bits set, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            core::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    i * BITS + bit
+                })
+            })
+        })
+    }
+
+    /// Sets every bit that's set in `other`
+    pub fn union_with(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Clears every bit that isn't set in `other`
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(other.words) {
+            *word &= other;
+        }
+    }
+
+    /// Clears every bit that's set in `other`
+    pub fn difference_with(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(other.words) {
+            *word &= !other;
+        }
+    }
+
+    /// Clears the bits from `len` on
+    fn truncate(&mut self, len: usize) {
+        for (i, word) in self.words.iter_mut().enumerate() {
+            let start = i * BITS;
+            if start >= len {
+                *word = 0;
+            } else if len - start < BITS {
+                *word &= (1 << (len - start)) - 1;
+            }
+        }
+    }
+}
+
+impl<const N: usize> Extend<usize> for BitSet<N> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl<const N: usize> BitOrAssign<&Self> for BitSet<N> {
+    fn bitor_assign(&mut self, rhs: &Self) {
+        self.union_with(rhs);
+    }
+}
+
+impl<const N: usize> BitAndAssign<&Self> for BitSet<N> {
+    fn bitand_assign(&mut self, rhs: &Self) {
+        self.intersect_with(rhs);
+    }
+}
+
+/// Moves every bit `n` places up, dropping those that pass the capacity
+impl<const N: usize> ShlAssign<usize> for BitSet<N> {
+    fn shl_assign(&mut self, n: usize) {
+        let (skip, shift) = (n / BITS, n % BITS);
+        for i in (0..N).rev() {
+            let low = i.checked_sub(skip).map_or(0, |j| self.words[j]);
+            let lower = i.checked_sub(skip + 1).map_or(0, |j| self.words[j]);
+            self.words[i] = match shift {
+                0 => low,
+                _ => low << shift | lower >> (BITS - shift),
+            };
+        }
+    }
+}
+
+/// Moves every bit `n` places down, dropping those that pass 0
+impl<const N: usize> ShrAssign<usize> for BitSet<N> {
+    fn shr_assign(&mut self, n: usize) {
+        let (skip, shift) = (n / BITS, n % BITS);
+        for i in 0..N {
+            let high = self.words.get(i + skip).copied().unwrap_or(0);
+            let higher = self.words.get(i + skip + 1).copied().unwrap_or(0);
+            self.words[i] = match shift {
+                0 => high,
+                _ => high >> shift | higher << (BITS - shift),
+            };
+        }
+    }
+}
+
+/// A `width` by `height` map of booleans, of up to `W * 32` columns and `H` rows.
+/// Positions are used like in a [`Grid`](crate::grid::Grid).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid<const W: usize, const H: usize> {
+    rows: [BitSet<W>; H],
+    width: usize,
+    height: usize,
+}
+
+impl<const W: usize, const H: usize> Default for BitGrid<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> BitGrid<W, H> {
+    /// An empty grid, to be filled with [`BitGrid::push_row`]
+    pub const fn new() -> Self {
+        Self {
+            rows: [const { BitSet::new() }; H],
+            width: 0,
+            height: 0,
+        }
+    }
+
+    /// A `width` by `height` grid with every bit cleared
+    pub fn with_size(width: usize, height: usize) -> Self {
+        assert!(
+            width <= W * BITS && height <= H,
+            "{width}x{height} doesn't fit"
+        );
+        Self {
+            width,
+            height,
+            ..Self::new()
+        }
+    }
+
+    /// Adds a row below the others, mapping each byte of `line` to a bit with `f`
+    pub fn push_row(&mut self, line: &[u8], mut f: impl FnMut(u8) -> bool) -> Result<(), Error> {
+        if self.height == H {
+            return Err(Error::TooTall);
+        }
+        if line.len() > W * BITS {
+            return Err(Error::TooWide);
+        }
+        if self.height > 0 && line.len() != self.width {
+            return Err(Error::Ragged);
+        }
+
+        let row = &mut self.rows[self.height];
+        for (x, &b) in line.iter().enumerate() {
+            row.set(x, f(b));
+        }
+        self.width = line.len();
+        self.height += 1;
+        Ok(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The largest number of bits the grid can hold
+    pub fn capacity(&self) -> usize {
+        W * BITS * H
+    }
+
+    /// Whether `pos` lies within the grid
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    /// The bit at `pos`, None outside the grid
+    pub fn get(&self, pos: Pos) -> Option<bool> {
+        let (x, y) = self.index_of(pos)?;
+        Some(self.rows[y].contains(x))
+    }
+
+    /// Whether the bit at `pos` is set, false outside the grid
+    pub fn is_set(&self, pos: Pos) -> bool {
+        self.get(pos) == Some(true)
+    }
+
+    /// Sets the bit at `pos`, returns whether it wasn't set before
+    ///
+    /// Panics if `pos` is outside the grid, like the following.
+    pub fn insert(&mut self, pos: Pos) -> bool {
+        let (x, y) = self.expect_index(pos);
+        self.rows[y].insert(x)
+    }
+
+    /// Clears the bit at `pos`, returns whether it was set
+    pub fn remove(&mut self, pos: Pos) -> bool {
+        let (x, y) = self.expect_index(pos);
+        self.rows[y].remove(x)
+    }
+
+    /// Flips the bit at `pos`, returns whether it's set now
+    pub fn toggle(&mut self, pos: Pos) -> bool {
+        let (x, y) = self.expect_index(pos);
+        self.rows[y].toggle(x)
+    }
+
+    pub fn set(&mut self, pos: Pos, value: bool) {
+        let (x, y) = self.expect_index(pos);
+        self.rows[y].set(x, value);
+    }
+
+    /// Clears every bit, keeping the size
+    pub fn clear(&mut self) {
+        self.rows.iter_mut().for_each(BitSet::clear);
+    }
+
+    /// The number of bits set
+    pub fn len(&self) -> usize {
+        self.rows().map(BitSet::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows().all(BitSet::is_empty)
+    }
+
+    /// The rows, top to bottom, with bit `x` for column `x`
+    pub fn rows(&self) -> impl Iterator<Item = &BitSet<W>> + '_ {
+        self.rows[..self.height].iter()
+    }
+
+    /// The positions of the bits set, row by row
+    pub fn iter(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.rows()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().map(move |x| Pos::new(x as isize, y as isize)))
+    }
+
+    /// Sets every bit that's set in `other`
+    pub fn union_with(&mut self, other: &Self) {
+        for (row, other) in self.rows.iter_mut().zip(&other.rows) {
+            row.union_with(other);
+        }
+    }
+
+    /// Clears every bit that isn't set in `other`
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (row, other) in self.rows.iter_mut().zip(&other.rows) {
+            row.intersect_with(other);
+        }
+    }
+
+    /// Moves every bit `n` columns to the right, dropping those that leave the grid
+    pub fn shift_right(&mut self, n: usize) {
+        for row in &mut self.rows[..self.height] {
+            *row <<= n;
+            row.truncate(self.width);
+        }
+    }
+
+    /// Moves every bit `n` columns to the left, dropping those that leave the grid
+    pub fn shift_left(&mut self, n: usize) {
+        for row in &mut self.rows[..self.height] {
+            *row >>= n;
+        }
+    }
+
+    /// Moves every row `n` rows down, dropping those that leave the grid
+    pub fn shift_down(&mut self, n: usize) {
+        let rows = &mut self.rows[..self.height];
+        let n = n.min(rows.len());
+        rows.rotate_right(n);
+        rows[..n].iter_mut().for_each(BitSet::clear);
+    }
+
+    /// Moves every row `n` rows up, dropping those that leave the grid
+    pub fn shift_up(&mut self, n: usize) {
+        let rows = &mut self.rows[..self.height];
+        let n = n.min(rows.len());
+        rows.rotate_left(n);
+        let len = rows.len();
+        rows[len - n..].iter_mut().for_each(BitSet::clear);
+    }
+
+    /// The grid as an image, see [`Pbm`]
+    pub fn pbm(&self) -> Pbm<'_, W, H> {
+        Pbm(self)
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<(usize, usize)> {
+        let x = usize::try_from(pos.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    fn expect_index(&self, pos: Pos) -> (usize, usize) {
+        self.index_of(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+/// Shows a [`BitGrid`] as a plain PBM image, netpbm's `P1` format, with the set bits
+/// black. Image tools read it as is, or turn it into a PNG, e.g. `convert tree.pbm
+/// tree.png`. Rows are wrapped at 70 pixels as the format asks, and the image ends with
+/// a blank line so it's easy to cut out of the rest of the output.
+pub struct Pbm<'a, const W: usize, const H: usize>(&'a BitGrid<W, H>);
+
+impl<const W: usize, const H: usize> Display for Pbm<'_, W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grid = self.0;
+        writeln!(f, "P1\n{} {}", grid.width, grid.height)?;
+        for row in grid.rows() {
+            for x in 0..grid.width {
+                if x > 0 && x % PBM_LINE == 0 {
+                    f.write_str("\n")?;
+                }
+                f.write_str(if row.contains(x) { "1" } else { "0" })?;
+            }
+            f.write_str("\n")?;
+        }
+        f.write_str("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+    use heapless::{String, Vec};
+
+    #[test]
+    fn test_set_and_clear() {
+        let mut set: BitSet<{ words(100) }> = BitSet::new();
+        assert_eq!(set.capacity(), 128);
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(99));
+        assert!(set.contains(99) && !set.contains(98) && !set.contains(1000));
+        assert!(!set.toggle(3));
+        assert!(set.toggle(40));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(40));
+        assert!(!set.remove(40));
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_iter_and_combine() {
+        let mut a: BitSet<2> = BitSet::new();
+        let mut b: BitSet<2> = BitSet::new();
+        a.extend([0, 31, 32, 63]);
+        b.extend([31, 33]);
+        let bits: Vec<usize, 4> = a.iter().collect();
+        assert_eq!(&bits[..], &[0, 31, 32, 63]);
+
+        let mut union = a.clone();
+        union |= &b;
+        assert_eq!(union.len(), 5);
+        let mut both = a.clone();
+        both &= &b;
+        assert_eq!(both.iter().collect::<Vec<usize, 4>>()[..], [31]);
+        a.difference_with(&b);
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn test_shifts() {
+        let mut set: BitSet<3> = BitSet::new();
+        set.extend([0, 30, 95]);
+        set <<= 33;
+        assert_eq!(set.iter().collect::<Vec<usize, 4>>()[..], [33, 63]);
+        set >>= 32;
+        assert_eq!(set.iter().collect::<Vec<usize, 4>>()[..], [1, 31]);
+        set >>= 2;
+        assert_eq!(set.iter().collect::<Vec<usize, 4>>()[..], [29]);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid: BitGrid<1, 4> = BitGrid::new();
+        for row in [b"#..", b".#.", b"..#"] {
+            grid.push_row(row, |b| b == b'#').unwrap();
+        }
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(Pos::new(1, 1)), Some(true));
+        assert_eq!(grid.get(Pos::new(3, 1)), None);
+        assert!(!grid.is_set(Pos::new(-1, 0)));
+        assert!(grid.insert(Pos::new(2, 0)));
+        assert!(!grid.toggle(Pos::new(2, 0)));
+        assert_eq!(grid.len(), 3);
+
+        let set: Vec<Pos, 4> = grid.iter().collect();
+        assert_eq!(&set[..], &[Pos::new(0, 0), Pos::new(1, 1), Pos::new(2, 2)]);
+        assert_eq!(grid.push_row(b"....", |_| true), Err(Error::Ragged));
+    }
+
+    #[test]
+    fn test_grid_shifts() {
+        let mut grid: BitGrid<1, 3> = BitGrid::with_size(3, 3);
+        grid.insert(Pos::new(0, 0));
+        grid.insert(Pos::new(2, 1));
+        grid.shift_right(1);
+        assert_eq!(grid.iter().collect::<Vec<Pos, 4>>()[..], [Pos::new(1, 0)]);
+        grid.shift_down(2);
+        assert_eq!(grid.iter().collect::<Vec<Pos, 4>>()[..], [Pos::new(1, 2)]);
+        grid.shift_up(1);
+        grid.shift_left(1);
+        assert_eq!(grid.iter().collect::<Vec<Pos, 4>>()[..], [Pos::new(0, 1)]);
+        grid.shift_left(1);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_pbm() {
+        let mut grid: BitGrid<1, 2> = BitGrid::new();
+        grid.push_row(b"#..", |b| b == b'#').unwrap();
+        grid.push_row(b".##", |b| b == b'#').unwrap();
+        let mut s: String<32> = String::new();
+        write!(s, "{}", grid.pbm()).unwrap();
+        assert_eq!(s, "P1\n3 2\n100\n011\n\n");
+
+        let mut wide: BitGrid<{ words(72) }, 1> = BitGrid::with_size(72, 1);
+        wide.insert(Pos::new(71, 0));
+        let mut s: String<128> = String::new();
+        write!(s, "{}", wide.pbm()).unwrap();
+        let lines: Vec<&str, 5> = s.lines().collect();
+        assert_eq!(lines[2].len(), 70);
+        assert_eq!(&lines[3..], &["01", ""]);
+    }
+}
@@ -0,0 +1,354 @@
+//! The upload protocol between the host and the device.
+//!
+//! The host sends the input in frames:
+//!
+//! ```text
+//! [SOF 0x02] [kind] [seq] [len u16 LE] [payload, at most MAX_PAYLOAD bytes] [CRC-32 u32 LE]
+//! ```
+//!
+//! The CRC covers everything from the kind up to the end of the payload.
+//! A `D` frame carries data, an `E` frame ends the input. The device answers every
+//! frame with a text line: `<ACK seq>` once it's been received, or `<NAK seq>` with
+//! the sequence number it expects when the frame was damaged or out of order.
+//! The host sends one frame at a time and retransmits it until it's acknowledged.
+//! Sequence numbers wrap around and start at 0 for every input.
+use core::fmt::{self, Display, Write as _};
+use embedded_io::{Error as _, ErrorKind, ErrorType, Read, Write};
+use heapless::{String, Vec};
+
+use crate::crc::Crc32;
+
+/// Start of Heading, marks the start of a frame
+pub const SOF: u8 = b'\x02';
+pub const MAX_PAYLOAD: usize = 256;
+/// SOF, kind, seq, length, payload and CRC
+pub const MAX_FRAME: usize = 1 + 1 + 1 + 2 + MAX_PAYLOAD + 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Data,
+    End,
+}
+
+impl Kind {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'D' => Some(Kind::Data),
+            b'E' => Some(Kind::End),
+            _ => None,
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Kind::Data => b'D',
+            Kind::End => b'E',
+        }
+    }
+}
+
+/// Builds a frame, panics if the payload is longer than [`MAX_PAYLOAD`]
+pub fn encode(kind: Kind, seq: u8, payload: &[u8]) -> Vec<u8, MAX_FRAME> {
+    assert!(payload.len() <= MAX_PAYLOAD, "Payload too long");
+
+    let mut frame = Vec::new();
+    let _ = frame.extend_from_slice(&[SOF, kind.to_byte(), seq]);
+    let _ = frame.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    let _ = frame.extend_from_slice(payload);
+    let crc = crate::crc::crc32(&frame[1..]);
+    let _ = frame.extend_from_slice(&crc.to_le_bytes());
+    frame
+}
+
+/// The device's answer to a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    Ack(u8),
+    /// Carries the sequence number the device expects
+    Nak(u8),
+}
+
+impl Reply {
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.strip_prefix('<')?.strip_suffix('>')?;
+        let (word, seq) = line.split_once(' ')?;
+        let seq = seq.parse().ok()?;
+        match word {
+            "ACK" => Some(Reply::Ack(seq)),
+            "NAK" => Some(Reply::Nak(seq)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reply::Ack(seq) => write!(f, "<ACK {seq}>"),
+            Reply::Nak(seq) => write!(f, "<NAK {seq}>"),
+        }
+    }
+}
+
+/// Reads the payloads of the frames arriving on `inner` and answers every frame.
+/// A read returns 0 bytes at the end of each input, and when `inner` does.
+pub struct Framed<T> {
+    inner: T,
+    payload: Vec<u8, MAX_PAYLOAD>,
+    /// How much of the payload has been read
+    pos: usize,
+    expected: u8,
+    /// The last accepted sequence number, to recognize retransmissions
+    last: Option<u8>,
+}
+
+impl<T> Framed<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            payload: Vec::new(),
+            pos: 0,
+            expected: 0,
+            last: None,
+        }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Read + Write> Framed<T> {
+    /// Receives frames until one is accepted, returns None if `inner` ended
+    fn receive(&mut self) -> Result<Option<Kind>, T::Error> {
+        loop {
+            self.payload.clear();
+            self.pos = 0;
+
+            // Skip to the start of a frame, e.g. after a damaged one
+            let mut byte = 0;
+            loop {
+                if !fill(&mut self.inner, core::slice::from_mut(&mut byte))? {
+                    return Ok(None);
+                }
+                if byte == SOF {
+                    break;
+                }
+            }
+
+            let mut head = [0u8; 4];
+            if !fill(&mut self.inner, &mut head)? {
+                return Ok(None);
+            }
+            let [kind, seq, len @ ..] = head;
+            let len = u16::from_le_bytes(len) as usize;
+            let Some(kind) = Kind::from_byte(kind).filter(|_| len <= MAX_PAYLOAD) else {
+                self.reply(Reply::Nak(self.expected))?;
+                continue;
+            };
+
+            let _ = self.payload.resize(len, 0);
+            let mut crc = [0u8; 4];
+            if !fill(&mut self.inner, &mut self.payload)? || !fill(&mut self.inner, &mut crc)? {
+                return Ok(None);
+            }
+
+            let mut expected_crc = Crc32::new();
+            expected_crc.update(&head);
+            expected_crc.update(&self.payload);
+            if expected_crc.finish() != u32::from_le_bytes(crc) {
+                self.payload.clear();
+                self.reply(Reply::Nak(self.expected))?;
+                continue;
+            }
+
+            if seq == self.expected {
+                self.reply(Reply::Ack(seq))?;
+                self.last = Some(seq);
+                self.expected = seq.wrapping_add(1);
+                if kind == Kind::End {
+                    self.payload.clear();
+                    self.expected = 0;
+                }
+                return Ok(Some(kind));
+            }
+
+            self.payload.clear();
+            if self.last == Some(seq) {
+                // Our ACK got lost, the host sent it again
+                self.reply(Reply::Ack(seq))?;
+            } else {
+                self.reply(Reply::Nak(self.expected))?;
+            }
+        }
+    }
+
+    fn reply(&mut self, reply: Reply) -> Result<(), T::Error> {
+        let mut line: String<16> = String::new();
+        let _ = writeln!(line, "{reply}");
+        self.inner.write_all(line.as_bytes())?;
+        self.inner.flush()
+    }
+}
+
+impl<T: ErrorType> ErrorType for Framed<T> {
+    type Error = T::Error;
+}
+
+impl<T: Read + Write> Read for Framed<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.pos == self.payload.len() {
+            match self.receive()? {
+                Some(Kind::Data) => {}
+                Some(Kind::End) | None => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.payload.len() - self.pos);
+        buf[..n].copy_from_slice(&self.payload[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Fills `buf` completely, returns false if `reader` ended before that
+fn fill<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool, R::Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Ok(false),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::LineReader;
+    use crate::mock::MockSerial;
+    use std::vec::Vec;
+
+    fn read_input(framed: &mut Framed<MockSerial>) -> Vec<u8> {
+        let mut res = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            match framed.read(&mut buf) {
+                Ok(0) => return res,
+                Ok(n) => res.extend_from_slice(&buf[..n]),
+                Err(e) => panic!("{e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_reply_roundtrip() {
+        for reply in [Reply::Ack(0), Reply::Nak(255)] {
+            let mut s: String<16> = String::new();
+            write!(s, "{reply}").unwrap();
+            assert_eq!(Reply::parse(&s), Some(reply));
+        }
+        assert_eq!(Reply::parse("<ACK>"), None);
+        assert_eq!(Reply::parse("<EOT>"), None);
+        assert_eq!(Reply::parse("<ACK 256>"), None);
+    }
+
+    #[test]
+    fn test_upload() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let serial = MockSerial::new().max_read(3).upload(&data);
+        let mut framed = Framed::new(serial);
+
+        assert_eq!(read_input(&mut framed), data);
+        assert_eq!(
+            framed.inner().written_str(),
+            "<ACK 0>\n<ACK 1>\n<ACK 2>\n<ACK 3>\n<ACK 4>\n"
+        );
+        assert!(framed.inner().is_drained());
+    }
+
+    #[test]
+    fn test_back_to_back_inputs() {
+        let serial = MockSerial::new()
+            .upload(b"DAY 1\n1 2\n")
+            .would_block()
+            .upload(b"DAY 2\n");
+        let mut framed = Framed::new(serial);
+        let mut lines = LineReader::<16>::new();
+
+        for expected in [&b"DAY 1"[..], b"1 2"] {
+            lines.clear();
+            assert!(lines.read_until_newline(&mut framed).unwrap());
+            assert_eq!(lines.line(), expected);
+        }
+        lines.clear();
+        assert!(!lines.read_until_newline(&mut framed).unwrap());
+
+        lines.clear();
+        assert!(lines.read_until_newline(&mut framed).unwrap());
+        assert_eq!(lines.line(), b"DAY 2");
+        lines.clear();
+        assert!(!lines.read_until_newline(&mut framed).unwrap());
+        assert_eq!(
+            framed.inner().written_str(),
+            "<ACK 0>\n<ACK 1>\n<ACK 0>\n<ACK 1>\n"
+        );
+    }
+
+    #[test]
+    fn test_damaged_frame_is_retransmitted() {
+        let mut damaged = encode(Kind::Data, 0, b"hello");
+        damaged[6] ^= 0x20;
+        let serial = MockSerial::new()
+            .bytes(&damaged)
+            .bytes(&encode(Kind::Data, 0, b"hello"))
+            .bytes(&encode(Kind::End, 1, &[]));
+        let mut framed = Framed::new(serial);
+
+        assert_eq!(read_input(&mut framed), b"hello");
+        assert_eq!(framed.inner().written_str(), "<NAK 0>\n<ACK 0>\n<ACK 1>\n");
+    }
+
+    #[test]
+    fn test_duplicate_frame_is_dropped() {
+        let serial = MockSerial::new()
+            .bytes(&encode(Kind::Data, 0, b"ab"))
+            .bytes(&encode(Kind::Data, 0, b"ab"))
+            .bytes(&encode(Kind::Data, 1, b"c"))
+            .bytes(&encode(Kind::End, 2, &[]));
+        let mut framed = Framed::new(serial);
+
+        assert_eq!(read_input(&mut framed), b"abc");
+        assert_eq!(
+            framed.inner().written_str(),
+            "<ACK 0>\n<ACK 0>\n<ACK 1>\n<ACK 2>\n"
+        );
+    }
+
+    #[test]
+    fn test_skips_noise_and_rejects_bad_frames() {
+        let mut too_long = encode(Kind::Data, 0, &[]);
+        too_long[3..5].copy_from_slice(&(MAX_PAYLOAD as u16 + 1).to_le_bytes());
+        let serial = MockSerial::new()
+            .bytes(b"boot noise\n")
+            .bytes(&too_long)
+            .bytes(&[SOF, b'X', 0, 0, 0])
+            .bytes(&encode(Kind::Data, 1, b"early"))
+            .bytes(&encode(Kind::Data, 0, b"ok"))
+            .bytes(&encode(Kind::End, 1, &[]));
+        let mut framed = Framed::new(serial);
+
+        assert_eq!(read_input(&mut framed), b"ok");
+        assert_eq!(
+            framed.inner().written_str(),
+            "<NAK 0>\n<NAK 0>\n<NAK 0>\n<ACK 0>\n<ACK 1>\n"
+        );
+    }
+}
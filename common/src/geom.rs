@@ -0,0 +1,367 @@
+//! Points and directions on a 2D map, with y growing downwards like the input's lines.
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// The integer types a [`Point`] can be made of
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The unsigned type of the same size, which holds any distance between two values
+    type Unsigned: Copy + Ord + Add<Output = Self::Unsigned>;
+
+    /// The distance between two values
+    fn distance(self, other: Self) -> Self::Unsigned;
+
+    /// The value wrapped into `0..size`
+    fn wrap(self, size: Self) -> Self;
+}
+
+/// The coordinates that can step in any direction
+pub trait Signed: Coord + Neg<Output = Self> {}
+
+macro_rules! coord {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                type Unsigned = $u;
+
+                fn distance(self, other: Self) -> $u {
+                    self.abs_diff(other)
+                }
+
+                fn wrap(self, size: Self) -> Self {
+                    self.rem_euclid(size)
+                }
+            }
+        )*
+    };
+}
+
+coord!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize
+);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for isize {}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Coord> Point<T> {
+    /// The number of orthogonal steps between two points
+    pub fn manhattan(self, other: Self) -> T::Unsigned {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// The number of steps between two points, diagonals included
+    pub fn chebyshev(self, other: Self) -> T::Unsigned {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// The point wrapped onto a torus of `size`, where leaving one edge enters the opposite one
+    pub fn wrap(self, size: Self) -> Self {
+        Self::new(self.x.wrap(size.x), self.y.wrap(size.y))
+    }
+
+    /// Adds `other` on a torus of `size`, see [`Point::wrap`]
+    pub fn wrapping_add(self, other: Self, size: Self) -> Self {
+        (self + other).wrap(size)
+    }
+}
+
+impl<T: Signed> Point<T> {
+    /// The point one step away in `dir`
+    pub fn step(self, dir: impl Into<Dir8>) -> Self {
+        self + dir.into().delta()
+    }
+
+    /// The orthogonal neighbours, clockwise from the one above
+    pub fn neighbours4(self) -> [Self; 4] {
+        Dir4::ALL.map(|dir| self.step(dir))
+    }
+
+    /// All neighbours, diagonals included, clockwise from the one above
+    pub fn neighbours8(self) -> [Self; 8] {
+        Dir8::ALL.map(|dir| self.step(dir))
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Coord> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Signed> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// One of the 4 orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Dir4 {
+    /// Clockwise from north, in the order of [`Dir4::idx`]
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    /// Parses an arrow `^>v<` or a compass letter `NESW`
+    pub fn from_u8(c: u8) -> Option<Self> {
+        match c {
+            b'^' | b'N' => Some(Dir4::N),
+            b'>' | b'E' => Some(Dir4::E),
+            b'v' | b'S' => Some(Dir4::S),
+            b'<' | b'W' => Some(Dir4::W),
+            _ => None,
+        }
+    }
+
+    /// A number in `0..4`, to index arrays by direction
+    pub fn idx(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_idx(idx: usize) -> Self {
+        Self::ALL[idx % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::from_idx(self.idx() + 1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::from_idx(self.idx() + 3)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_idx(self.idx() + 2)
+    }
+
+    /// How many quarter turns it takes to face `other`: 0, 1 or 2
+    pub fn turns_to(self, other: Self) -> u8 {
+        match (other.idx() + 4 - self.idx()) % 4 {
+            0 => 0,
+            2 => 2,
+            _ => 1,
+        }
+    }
+
+    /// The offset of one step in this direction
+    pub fn delta<T: Signed>(self) -> Point<T> {
+        Dir8::from(self).delta()
+    }
+}
+
+/// One of the 8 directions, diagonals included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// Clockwise from north, in the order of [`Dir8::idx`]
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    /// A number in `0..8`, to index arrays by direction
+    pub fn idx(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_idx(idx: usize) -> Self {
+        Self::ALL[idx % 8]
+    }
+
+    /// Turns 45 degrees clockwise
+    pub fn turn_right(self) -> Self {
+        Self::from_idx(self.idx() + 1)
+    }
+
+    /// Turns 45 degrees counterclockwise
+    pub fn turn_left(self) -> Self {
+        Self::from_idx(self.idx() + 7)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_idx(self.idx() + 4)
+    }
+
+    /// The offset of one step in this direction
+    pub fn delta<T: Signed>(self) -> Point<T> {
+        let (one, zero) = (T::ONE, T::ZERO);
+        let (x, y) = match self {
+            Dir8::N => (zero, -one),
+            Dir8::NE => (one, -one),
+            Dir8::E => (one, zero),
+            Dir8::SE => (one, one),
+            Dir8::S => (zero, one),
+            Dir8::SW => (-one, one),
+            Dir8::W => (-one, zero),
+            Dir8::NW => (-one, -one),
+        };
+        Point::new(x, y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::from_idx(dir.idx() * 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3i16, -2);
+        let b = Point::new(1, 5);
+        assert_eq!(a + b, Point::new(4, 3));
+        assert_eq!(a - b, Point::new(2, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(Point::from((1, 2)), Point::new(1, 2));
+        assert_eq!(<(u8, u8)>::from(Point::new(1, 2)), (1, 2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1u8, 8);
+        let b = Point::new(4, 2);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(b.manhattan(a), 9);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point::new(-3i8, 0).manhattan(Point::new(3, 0)), 6);
+        // Further apart than the coordinates' type reaches
+        assert_eq!((-100i8).distance(100), 200u8);
+        assert_eq!(Point::new(-100i8, 0).chebyshev(Point::new(100, 5)), 200);
+        assert_eq!(i64::MIN.distance(i64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let size = Point::new(11i16, 7);
+        assert_eq!(Point::new(-1, 7).wrap(size), Point::new(10, 0));
+        let pos = Point::new(2, 4);
+        let vel = Point::new(2, -3);
+        assert_eq!(pos.wrapping_add(vel * 5, size), Point::new(1, 3));
+    }
+
+    #[test]
+    fn test_dir4() {
+        let dirs = b"^>v<NESW".map(|c| Dir4::from_u8(c).unwrap());
+        assert_eq!(dirs[..4], Dir4::ALL);
+        assert_eq!(dirs[4..], Dir4::ALL);
+        assert_eq!(Dir4::from_u8(b'x'), None);
+
+        assert_eq!(Dir4::N.turn_right(), Dir4::E);
+        assert_eq!(Dir4::N.turn_left(), Dir4::W);
+        assert_eq!(Dir4::E.opposite(), Dir4::W);
+        assert_eq!(Dir4::N.turns_to(Dir4::N), 0);
+        assert_eq!(Dir4::N.turns_to(Dir4::W), 1);
+        assert_eq!(Dir4::W.turns_to(Dir4::E), 2);
+
+        assert_eq!(Point::new(0isize, 0).step(Dir4::N), Point::new(0, -1));
+        assert_eq!(Dir4::W.delta::<i8>(), Point::new(-1, 0));
+    }
+
+    #[test]
+    fn test_dir8() {
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+        assert_eq!(Dir8::SW.opposite(), Dir8::NE);
+        assert_eq!(Dir8::from(Dir4::S), Dir8::S);
+
+        let p = Point::new(5i32, 5);
+        assert_eq!(p.neighbours4().len(), 4);
+        assert!(p.neighbours8().iter().all(|n| n.chebyshev(p) == 1));
+        assert_eq!(p.step(Dir8::SE), Point::new(6, 6));
+    }
+}
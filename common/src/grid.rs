@@ -0,0 +1,313 @@
+//! A fixed-capacity 2D grid, for the days whose input is a map.
+//!
+//! The cells are kept in one contiguous array of `H` rows of `W` cells, of which the
+//! first `height` rows and `width` columns are in use. Positions are signed points,
+//! so stepping off the edge of the map is just a `get` that returns None.
+use core::convert::Infallible;
+use core::fmt::{self, Debug, Display};
+use core::ops::{Index, IndexMut};
+use embedded_io::Read;
+
+use crate::geom::Point;
+use crate::io::LineReader;
+
+/// A position in a grid
+pub type Pos = Point<isize>;
+
+/// Why a grid couldn't be built
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error<E = Infallible> {
+    Read(E),
+    /// A row is longer than `W`
+    TooWide,
+    /// There are more than `H` rows
+    TooTall,
+    /// A row isn't as long as the first one
+    Ragged,
+}
+
+impl<E: Debug> Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Read(e) => write!(f, "Error reading! {e:?}"),
+            Error::TooWide => f.write_str("Row too long for the grid"),
+            Error::TooTall => f.write_str("Too many rows for the grid"),
+            Error::Ragged => f.write_str("Rows of different lengths"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T, const W: usize, const H: usize> {
+    cells: [[T; W]; H],
+    width: usize,
+    height: usize,
+}
+
+impl<T: Copy + Default, const W: usize, const H: usize> Default for Grid<T, W, H> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy, const W: usize, const H: usize> Grid<T, W, H> {
+    /// An empty grid, to be filled with [`Grid::push_row`]
+    pub fn new(fill: T) -> Self {
+        Self {
+            cells: [[fill; W]; H],
+            width: 0,
+            height: 0,
+        }
+    }
+
+    /// A `width` by `height` grid with every cell set to `fill`
+    pub fn filled(width: usize, height: usize, fill: T) -> Self {
+        assert!(width <= W && height <= H, "{width}x{height} doesn't fit");
+        Self {
+            cells: [[fill; W]; H],
+            width,
+            height,
+        }
+    }
+
+    /// Reads rows from `reader` until a blank line or the end of the input, mapping
+    /// each byte to a cell with `f`. Whatever follows the blank line is left unread.
+    pub fn read<R, const N: usize>(
+        reader: &mut R,
+        lines: &mut LineReader<N>,
+        fill: T,
+        mut f: impl FnMut(u8) -> T,
+    ) -> Result<Self, Error<R::Error>>
+    where
+        R: Read,
+    {
+        let mut grid = Self::new(fill);
+        loop {
+            lines.clear();
+            if !lines.read_until_newline(reader).map_err(Error::Read)? || lines.line().is_empty() {
+                return Ok(grid);
+            }
+            if lines.is_partial() {
+                return Err(Error::TooWide);
+            }
+            grid.push_row(lines.line(), &mut f).map_err(|e| match e {
+                Error::Read(never) => match never {},
+                Error::TooWide => Error::TooWide,
+                Error::TooTall => Error::TooTall,
+                Error::Ragged => Error::Ragged,
+            })?;
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> Grid<T, W, H> {
+    /// Adds a row below the others, mapping each byte of `line` to a cell with `f`
+    pub fn push_row(&mut self, line: &[u8], f: impl FnMut(u8) -> T) -> Result<(), Error> {
+        if self.height == H {
+            return Err(Error::TooTall);
+        }
+        if line.len() > W {
+            return Err(Error::TooWide);
+        }
+        if self.height > 0 && line.len() != self.width {
+            return Err(Error::Ragged);
+        }
+
+        let row = &mut self.cells[self.height];
+        for (cell, value) in row.iter_mut().zip(line.iter().copied().map(f)) {
+            *cell = value;
+        }
+        self.width = line.len();
+        self.height += 1;
+        Ok(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` lies within the grid
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        let (x, y) = self.index_of(pos)?;
+        Some(&self.cells[y][x])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        let (x, y) = self.index_of(pos)?;
+        Some(&mut self.cells[y][x])
+    }
+
+    /// The cells of row `y`, left to right
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y][..self.width])
+    }
+
+    /// The cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        (x < self.width).then(|| self.cells[..self.height].iter().map(move |row| &row[x]))
+    }
+
+    /// The rows, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells[..self.height]
+            .iter()
+            .map(|row| &row[..self.width])
+    }
+
+    /// The columns, left to right, each top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.cells[..self.height].iter().map(move |row| &row[x]))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, cell)| (Pos::new(x as isize, y as isize), cell))
+        })
+    }
+
+    /// The position of the first cell, row by row, that matches `pred`
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| pred(cell).then_some(pos))
+    }
+
+    /// The orthogonal neighbours of `pos` that lie within the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours(pos.neighbours4())
+    }
+
+    /// All neighbours of `pos`, diagonals included, that lie within the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours(pos.neighbours8())
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        candidates: [Pos; N],
+    ) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        candidates
+            .into_iter()
+            .filter_map(|next| self.get(next).map(|cell| (next, cell)))
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<(usize, usize)> {
+        let x = usize::try_from(pos.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<Pos> for Grid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<Pos> for Grid<T, W, H> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    const MAP: &[u8] = b"#.#\n..S\n#..\n";
+
+    fn parse(map: &[u8]) -> Grid<u8, 4, 4> {
+        let mut grid = Grid::default();
+        for line in map.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+            grid.push_row(line, |b| b).unwrap();
+        }
+        grid
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = parse(MAP);
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(Pos::new(2, 1)), Some(&b'S'));
+        assert_eq!(grid.get(Pos::new(-1, 0)), None);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 3)), None);
+
+        *grid.get_mut(Pos::new(1, 1)).unwrap() = b'#';
+        grid[Pos::new(1, 2)] = b'#';
+        assert_eq!(grid.row(1), Some(&b".#S"[..]));
+        assert_eq!(grid.row(2), Some(&b"##."[..]));
+        assert_eq!(grid.row(3), None);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = parse(MAP);
+        let rows: Vec<&[u8], 4> = grid.rows().collect();
+        assert_eq!(&rows[..], &[&b"#.#"[..], b"..S", b"#.."]);
+
+        let column: Vec<u8, 4> = grid.column(2).unwrap().copied().collect();
+        assert_eq!(&column[..], b"#S.");
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn test_find_and_neighbours() {
+        let grid = parse(MAP);
+        assert_eq!(grid.find(|&c| c == b'S'), Some(Pos::new(2, 1)));
+        assert_eq!(grid.find(|&c| c == b'E'), None);
+
+        let open: Vec<Pos, 4> = grid
+            .neighbours4(Pos::new(2, 1))
+            .filter(|(_, &c)| c == b'.')
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(&open[..], &[Pos::new(2, 2), Pos::new(1, 1)]);
+        assert_eq!(grid.neighbours8(Pos::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_push_row_errors() {
+        let mut grid: Grid<u8, 2, 2> = Grid::default();
+        assert_eq!(grid.push_row(b"...", |b| b), Err(Error::TooWide));
+        grid.push_row(b"..", |b| b).unwrap();
+        assert_eq!(grid.push_row(b".", |b| b), Err(Error::Ragged));
+        grid.push_row(b"..", |b| b).unwrap();
+        assert_eq!(grid.push_row(b"..", |b| b), Err(Error::TooTall));
+    }
+
+    #[test]
+    fn test_read_until_blank_line() {
+        let mut input = &b"#.\n.#\n\n<>\n"[..];
+        let mut lines = LineReader::<8>::new();
+        let grid: Grid<bool, 2, 2> =
+            Grid::read(&mut input, &mut lines, false, |b| b == b'#').unwrap();
+        assert_eq!(grid, {
+            let mut expected = Grid::filled(2, 2, false);
+            expected[Pos::new(0, 0)] = true;
+            expected[Pos::new(1, 1)] = true;
+            expected
+        });
+
+        lines.clear();
+        assert!(lines.read_until_newline(&mut input).unwrap());
+        assert_eq!(lines.line(), b"<>");
+    }
+}
@@ -1,19 +1,37 @@
-use embedded_io::{ErrorType, Read};
+use embedded_io::{Error, ErrorKind, ErrorType, Read};
 use heapless::Vec;
 
+/// End of Transmission, ends an input on plain text transports
+pub const EOT: u8 = b'\x04';
+
 /// A line reader that buffers input until a newline is found
 pub struct LineReader<const N: usize> {
     buffer: Vec<u8, N>,
+    /// A byte that didn't fit in the buffer anymore
+    pending: Option<u8>,
+    /// The input ended right after the current line, without a newline
+    ended: bool,
+}
+
+impl<const N: usize> Default for LineReader<N> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<const N: usize> LineReader<N> {
     /// Creates a new LineReader with a fixed-size buffer
     pub fn new() -> Self {
-        Self { buffer: Vec::new() }
+        Self {
+            buffer: Vec::new(),
+            pending: None,
+            ended: false,
+        }
     }
 
     /// Reads from the given reader until a newline is found or the buffer is full
-    /// Returns Ok(true) if a complete line was read, Ok(false) if more data is needed
+    /// Returns Ok(true) if a line was read, Ok(false) once the input has ended,
+    /// i.e. when the reader returns 0 bytes.
     /// The line can be accessed via the line() method
     pub fn read_until_newline<R>(&mut self, reader: &mut R) -> Result<bool, R::Error>
     where
@@ -24,11 +42,23 @@ impl<const N: usize> LineReader<N> {
             return Ok(true);
         }
 
+        if let Some(byte) = self.pending.take() {
+            let _ = self.buffer.push(byte);
+        } else if self.ended {
+            self.ended = false;
+            return Ok(false);
+        }
+
         let mut byte_buf = [0u8; 1];
 
         loop {
             match reader.read(&mut byte_buf) {
-                Ok(0) => return Ok(!self.buffer.is_empty()), // EOF
+                Ok(0) if self.buffer.is_empty() => return Ok(false),
+                // The last line didn't end with a newline, the input ends after it
+                Ok(0) => {
+                    self.ended = true;
+                    return Ok(true);
+                }
                 Ok(_) => {
                     let byte = byte_buf[0];
 
@@ -37,20 +67,20 @@ impl<const N: usize> LineReader<N> {
                         return Ok(true);
                     }
 
-                    if byte == b'\x04' {
-                        return Ok(false);
-                    }
-
                     // Skip carriage return
                     if byte == b'\r' {
                         continue;
                     }
 
-                    // Try to push byte to buffer
+                    // Try to push byte to buffer. If it's full, keep the byte for the next
+                    // line instead of dropping it.
                     if self.buffer.push(byte).is_err() {
+                        self.pending = Some(byte);
                         return Ok(true); // Buffer full
                     }
                 }
+                // Nothing was read, e.g. because no data was available yet
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
                 Err(e) => return Err(e),
             }
         }
@@ -61,12 +91,51 @@ impl<const N: usize> LineReader<N> {
         self.buffer.as_slice()
     }
 
+    /// Whether the current line was cut off because the buffer is full,
+    /// the rest of it follows on the next read
+    pub fn is_partial(&self) -> bool {
+        self.pending.is_some()
+    }
+
     /// Clears the internal buffer
     pub fn clear(&mut self) {
         self.buffer.clear();
     }
 }
 
+/// Ends an input at every End of Transmission byte, for transports without framing.
+/// Reads a single byte at a time, so nothing after the EOT is consumed.
+pub struct EotReader<R> {
+    inner: R,
+}
+
+impl<R> EotReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+impl<R: ErrorType> ErrorType for EotReader<R> {
+    type Error = R::Error;
+}
+
+impl<R: Read> Read for EotReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let Some(byte) = buf.first_mut() else {
+            return Ok(0);
+        };
+
+        match self.inner.read(core::slice::from_mut(byte))? {
+            1 if *byte == EOT => Ok(0),
+            n => Ok(n),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,5 +192,59 @@ mod tests {
 
         assert!(line_reader.read_until_newline(&mut reader).unwrap());
         assert_eq!(line_reader.line(), b"ThisIsAV");
+        assert!(line_reader.is_partial());
+
+        // The rest of the line is handed out on the next read, without losing bytes
+        line_reader.clear();
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"eryLongL");
+
+        line_reader.clear();
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"ine");
+        assert!(!line_reader.is_partial());
+    }
+
+    #[test]
+    fn test_line_fills_buffer_exactly() {
+        let mut reader = MockReader::new(b"Exactly8\nNext\n");
+        let mut line_reader = LineReader::<8>::new();
+
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"Exactly8");
+        assert!(!line_reader.is_partial());
+
+        line_reader.clear();
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"Next");
+    }
+
+    #[test]
+    fn test_last_line_without_newline() {
+        let mut reader = MockReader::new(b"Last");
+        let mut line_reader = LineReader::<8>::new();
+
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"Last");
+
+        line_reader.clear();
+        assert!(!line_reader.read_until_newline(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn test_eot_ends_input() {
+        let mut reader = EotReader::new(MockReader::new(b"One\x04Two\n\x04"));
+        let mut line_reader = LineReader::<8>::new();
+
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"One");
+        line_reader.clear();
+        assert!(!line_reader.read_until_newline(&mut reader).unwrap());
+
+        // The next input follows the EOT
+        assert!(line_reader.read_until_newline(&mut reader).unwrap());
+        assert_eq!(line_reader.line(), b"Two");
+        line_reader.clear();
+        assert!(!line_reader.read_until_newline(&mut reader).unwrap());
     }
 }
@@ -1,5 +1,25 @@
 #![no_std]
+#[cfg(any(test, feature = "mock"))]
+extern crate std;
+
+pub mod bits;
+pub mod crc;
+pub mod frame;
+pub mod geom;
+pub mod grid;
 pub mod io;
 pub mod iter;
+pub mod memo;
+pub mod memory;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod num;
+pub mod output;
+pub mod params;
+pub mod path;
+pub mod region;
+pub mod registry;
+pub mod runner;
+pub mod solution;
 pub mod solver;
+pub mod timing;
@@ -0,0 +1,421 @@
+//! A fixed-capacity cache for memoised recursion, that evicts old entries when it's
+//! full instead of failing to insert.
+//!
+//! The entries live in an open addressing hash table of `N` slots. Which entry makes
+//! room for a new one depends on the [`Eviction`] policy, chosen when the cache is made:
+//!
+//! - [`Eviction::Lru`] evicts the entry that was used the longest time ago.
+//! - [`Eviction::Clock`] approximates that more cheaply: it sweeps a hand over the
+//!   slots and evicts the first entry that wasn't used since the hand last passed.
+//! - [`Eviction::DirectMapped`] gives every key a single slot, and overwrites whatever
+//!   is there. It's the fastest, but two keys that share a slot keep evicting each other.
+//!
+//! With LRU and clock the table is kept at most 7/8 full, so looking up a key that's
+//! not there stays quick.
+use core::hash::{Hash, Hasher};
+
+/// Which entry to evict when the cache is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eviction {
+    Lru,
+    Clock,
+    DirectMapped,
+}
+
+/// How well the cache has done
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+}
+
+impl Stats {
+    /// The share of lookups that were hits, between 0 and 1
+    pub fn hit_rate(&self) -> f32 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f32 / lookups as f32,
+        }
+    }
+}
+
+/// No slot, the end of the LRU list
+const NIL: u16 = u16::MAX;
+
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    /// The next more recently used slot
+    newer: u16,
+    /// The next less recently used slot
+    older: u16,
+    /// Whether the entry was used since the clock hand last passed
+    referenced: bool,
+}
+
+/// A cache of values by key, in a table of `N` slots
+pub struct Cache<K, V, const N: usize> {
+    slots: [Option<Slot<K, V>>; N],
+    eviction: Eviction,
+    len: usize,
+    newest: u16,
+    oldest: u16,
+    hand: usize,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq, V: Clone, const N: usize> Cache<K, V, N> {
+    pub fn new(eviction: Eviction) -> Self {
+        assert!(N > 0 && N < NIL as usize, "A cache has 1 to 65534 slots");
+        Self {
+            slots: [const { None }; N],
+            eviction,
+            len: 0,
+            newest: NIL,
+            oldest: NIL,
+            hand: 0,
+            stats: Stats::default(),
+        }
+    }
+
+    /// The number of entries
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The largest number of entries the cache keeps
+    pub fn capacity(&self) -> usize {
+        match self.eviction {
+            Eviction::DirectMapped => N,
+            Eviction::Lru | Eviction::Clock => (N - N / 8).max(1),
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Forgets every entry, but not the statistics
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+        self.newest = NIL;
+        self.oldest = NIL;
+        self.hand = 0;
+    }
+
+    /// The value cached for `key`, counting as a use of it
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let Some(i) = self.find(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.stats.hits += 1;
+        self.touch(i);
+        self.slots[i].as_ref().map(|slot| slot.value.clone())
+    }
+
+    /// Caches `value` for `key`, evicting an entry if the cache is full
+    pub fn insert(&mut self, key: K, value: V) {
+        if let Some(i) = self.find(&key) {
+            if let Some(slot) = &mut self.slots[i] {
+                slot.value = value;
+            }
+            self.touch(i);
+            return;
+        }
+
+        let i = match self.eviction {
+            Eviction::DirectMapped => {
+                let i = self.home(&key);
+                if self.slots[i].take().is_some() {
+                    self.len -= 1;
+                    self.stats.evictions += 1;
+                }
+                i
+            }
+            Eviction::Lru | Eviction::Clock => {
+                if self.len == self.capacity() {
+                    self.evict();
+                }
+                let mut i = self.home(&key);
+                while self.slots[i].is_some() {
+                    i = (i + 1) % N;
+                }
+                i
+            }
+        };
+        self.slots[i] = Some(Slot {
+            key,
+            value,
+            newer: NIL,
+            older: NIL,
+            referenced: false,
+        });
+        self.len += 1;
+        if self.eviction == Eviction::Lru {
+            self.push_newest(i);
+        }
+    }
+
+    /// The value cached for `key`, or else the one `f` computes, which is then cached.
+    /// `f` gets the cache to memoise the recursive calls it makes with.
+    pub fn memoize(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    fn home(&self, key: &K) -> usize {
+        let mut hasher = Fnv::default();
+        key.hash(&mut hasher);
+        hasher.finish() as usize % N
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        let home = self.home(key);
+        let probes = match self.eviction {
+            Eviction::DirectMapped => 1,
+            Eviction::Lru | Eviction::Clock => N,
+        };
+        (0..probes)
+            .map(|probe| (home + probe) % N)
+            .map_while(|i| self.slots[i].as_ref().map(|slot| (i, slot)))
+            .find_map(|(i, slot)| (slot.key == *key).then_some(i))
+    }
+
+    /// Marks the entry in slot `i` as just used
+    fn touch(&mut self, i: usize) {
+        match self.eviction {
+            Eviction::Lru => {
+                self.unlink(i);
+                self.push_newest(i);
+            }
+            Eviction::Clock => {
+                if let Some(slot) = &mut self.slots[i] {
+                    slot.referenced = true;
+                }
+            }
+            Eviction::DirectMapped => {}
+        }
+    }
+
+    fn evict(&mut self) {
+        let victim = match self.eviction {
+            Eviction::Lru => self.oldest as usize,
+            Eviction::Clock => loop {
+                let i = self.hand;
+                self.hand = (self.hand + 1) % N;
+                match &mut self.slots[i] {
+                    Some(slot) if slot.referenced => slot.referenced = false,
+                    Some(_) => break i,
+                    None => {}
+                }
+            },
+            Eviction::DirectMapped => unreachable!("Direct mapped entries are overwritten"),
+        };
+        self.remove(victim);
+        self.stats.evictions += 1;
+    }
+
+    /// Removes the entry in slot `i`, moving the entries after it that were pushed
+    /// past their home slot back, so every entry is still found by probing from home
+    fn remove(&mut self, mut i: usize) {
+        if self.eviction == Eviction::Lru {
+            self.unlink(i);
+        }
+        self.slots[i] = None;
+        self.len -= 1;
+
+        let mut j = i;
+        loop {
+            j = (j + 1) % N;
+            let Some(slot) = &self.slots[j] else {
+                return;
+            };
+            // The entry in `j` can move to `i` unless its home lies cyclically in (i, j]
+            let home = self.home(&slot.key);
+            let stays = match i <= j {
+                true => i < home && home <= j,
+                false => i < home || home <= j,
+            };
+            if !stays {
+                self.slots[i] = self.slots[j].take();
+                self.relink(j, i);
+                i = j;
+            }
+        }
+    }
+
+    fn push_newest(&mut self, i: usize) {
+        let old_newest = self.newest;
+        if let Some(slot) = &mut self.slots[i] {
+            slot.newer = NIL;
+            slot.older = old_newest;
+        }
+        match self.slots.get_mut(old_newest as usize) {
+            Some(Some(newest)) => newest.newer = i as u16,
+            _ => self.oldest = i as u16,
+        }
+        self.newest = i as u16;
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let Some(slot) = &self.slots[i] else {
+            return;
+        };
+        let (newer, older) = (slot.newer, slot.older);
+        match self.slots.get_mut(newer as usize) {
+            Some(Some(slot)) => slot.older = older,
+            _ => self.newest = older,
+        }
+        match self.slots.get_mut(older as usize) {
+            Some(Some(slot)) => slot.newer = newer,
+            _ => self.oldest = newer,
+        }
+    }
+
+    /// Points the LRU list at slot `to` where it pointed at `from`
+    fn relink(&mut self, from: usize, to: usize) {
+        if self.eviction != Eviction::Lru {
+            return;
+        }
+        let Some(slot) = &self.slots[to] else {
+            return;
+        };
+        let (newer, older) = (slot.newer, slot.older);
+        match self.slots.get_mut(newer as usize) {
+            Some(Some(slot)) => slot.older = to as u16,
+            _ => self.newest = to as u16,
+        }
+        match self.slots.get_mut(older as usize) {
+            Some(Some(slot)) => slot.newer = to as u16,
+            _ => self.oldest = to as u16,
+        }
+        debug_assert!(self.newest as usize != from && self.oldest as usize != from);
+    }
+}
+
+/// The 32 bit FNV-1a hash, which is quick for the small keys of a cache
+struct Fnv(u32);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0x811c_9dc5)
+    }
+}
+
+impl Hasher for Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u32).wrapping_mul(0x0100_0193);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru() {
+        let mut cache: Cache<u32, u32, 4> = Cache::new(Eviction::Lru);
+        assert_eq!(cache.capacity(), 4);
+        for i in 0..4 {
+            cache.insert(i, i * 10);
+        }
+        assert_eq!(cache.get(&0), Some(0));
+        cache.insert(4, 40);
+        // 1 was used longest ago, 0 was just used
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&0), Some(0));
+        assert_eq!(cache.len(), 4);
+        cache.insert(5, 50);
+        assert_eq!(cache.get(&2), None);
+        for i in [0, 3, 4, 5] {
+            assert_eq!(cache.get(&i), Some(i * 10));
+        }
+        assert_eq!(
+            cache.stats(),
+            Stats {
+                hits: 6,
+                misses: 2,
+                evictions: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut cache: Cache<u32, u32, 4> = Cache::new(Eviction::Clock);
+        for i in 0..4 {
+            cache.insert(i, i);
+        }
+        cache.get(&0);
+        cache.get(&1);
+        cache.insert(4, 4);
+        // The hand passes over the entries that were used, and evicts another
+        assert_eq!(cache.len(), 4);
+        assert_eq!(cache.stats().evictions, 1);
+        assert!(cache.get(&4).is_some());
+        assert_eq!((0..5).filter(|i| cache.get(i).is_some()).count(), 4);
+    }
+
+    #[test]
+    fn test_direct_mapped() {
+        let mut cache: Cache<u32, u32, 2> = Cache::new(Eviction::DirectMapped);
+        for i in 0..10 {
+            cache.insert(i, i);
+            assert_eq!(cache.get(&i), Some(i));
+        }
+        assert!(cache.len() <= 2);
+        assert!(cache.stats().evictions >= 8);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_many_evictions_keep_entries_findable() {
+        for eviction in [Eviction::Lru, Eviction::Clock] {
+            let mut cache: Cache<u32, u32, 64> = Cache::new(eviction);
+            for i in 0..1000 {
+                cache.insert(i, i + 1);
+                assert_eq!(cache.get(&i), Some(i + 1));
+            }
+            assert_eq!(cache.len(), cache.capacity());
+            // The most recent entries are still there with LRU
+            if eviction == Eviction::Lru {
+                for i in 1000 - 56..1000 {
+                    assert_eq!(cache.get(&i), Some(i + 1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_memoize() {
+        fn fib(n: u64, cache: &mut Cache<u64, u64, 16>) -> u64 {
+            match n {
+                0 | 1 => n,
+                _ => cache.memoize(n, |cache| fib(n - 1, cache) + fib(n - 2, cache)),
+            }
+        }
+
+        // Far more values than fit, the cache only holds the recent ones
+        let mut cache = Cache::new(Eviction::Lru);
+        assert_eq!(fib(90, &mut cache), 2_880_067_194_370_816_120);
+        assert_eq!(cache.stats().misses, 89);
+    }
+}
@@ -0,0 +1,137 @@
+//! Measures memory use, to right-size the fixed capacities instead of guessing.
+//!
+//! Everything a day keeps lives on the stack, so the stack's high-water mark is its
+//! peak memory use. It's found by painting the free part of the stack with a pattern
+//! before a step, and looking for the deepest word that was overwritten after it.
+//! The fixed-capacity containers report how full they got through
+//! [`Solution::usage`](crate::solution::Solution::usage).
+use core::ptr;
+
+/// What unused stack looks like
+const PAINT: u32 = 0xA5A5_5A5A;
+/// Left alone below the current stack pointer, for the painting itself and interrupts
+const MARGIN: usize = 1024;
+
+/// How full a fixed-capacity container got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub name: &'static str,
+    pub peak: usize,
+    pub capacity: usize,
+}
+
+/// Tracks the most elements a container held, for containers that shrink again
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HighWater(usize);
+
+impl HighWater {
+    pub fn update(&mut self, len: usize) {
+        self.0 = self.0.max(len);
+    }
+
+    pub fn get(&self) -> usize {
+        self.0
+    }
+}
+
+/// A stack that grows down from `top` to `bottom`
+pub struct Stack {
+    top: usize,
+    bottom: usize,
+}
+
+impl Stack {
+    /// # Safety
+    /// `bottom..top` must be the stack of the thread that uses this, and nothing but
+    /// the stack may live in that range.
+    pub unsafe fn new(top: usize, bottom: usize) -> Self {
+        Self {
+            top,
+            bottom: bottom.next_multiple_of(4),
+        }
+    }
+
+    /// The stack's size in bytes
+    pub fn size(&self) -> usize {
+        self.top - self.bottom
+    }
+
+    /// Paints the free part of the stack, below the caller's frame
+    #[inline(never)]
+    pub fn paint(&self) {
+        let end = stack_pointer().saturating_sub(MARGIN);
+        let mut addr = self.bottom;
+        while addr + 4 <= end {
+            // SAFETY: Nothing lives below the stack pointer, see `new`
+            unsafe { ptr::write_volatile(addr as *mut u32, PAINT) };
+            addr += 4;
+        }
+    }
+
+    /// The most bytes that were in use since the last [`Stack::paint`].
+    /// If the stack was never painted, that's all of it.
+    pub fn peak(&self) -> usize {
+        let mut addr = self.bottom;
+        // SAFETY: The stack is ours to read, see `new`
+        while addr < self.top && unsafe { ptr::read_volatile(addr as *const u32) } == PAINT {
+            addr += 4;
+        }
+        self.top - addr
+    }
+}
+
+/// Roughly where the stack pointer is, the address of a local is close enough
+#[inline(always)]
+pub fn stack_pointer() -> usize {
+    let marker = 0u8;
+    ptr::addr_of!(marker) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::hint::black_box;
+
+    /// Puts `n` bytes on the stack
+    #[inline(never)]
+    fn use_stack(n: usize) -> u8 {
+        let mut buf = [0u8; 64 * 1024];
+        for (i, b) in buf[..n].iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        black_box(&mut buf);
+        buf[n / 2]
+    }
+
+    #[test]
+    fn test_high_water() {
+        let mut hw = HighWater::default();
+        hw.update(3);
+        hw.update(1);
+        assert_eq!(hw.get(), 3);
+    }
+
+    #[test]
+    fn test_stack_peak() {
+        std::thread::Builder::new()
+            .stack_size(1024 * 1024)
+            .spawn(|| {
+                let top = stack_pointer();
+                // Stay well clear of the guard page at the end of the thread's stack
+                let stack = unsafe { Stack::new(top, top - 512 * 1024) };
+
+                stack.paint();
+                let idle = stack.peak();
+                assert!(idle < 16 * 1024, "{idle}");
+
+                stack.paint();
+                black_box(use_stack(black_box(48 * 1024)));
+                let busy = stack.peak();
+                assert!(busy >= 48 * 1024, "{busy}");
+                assert!(busy < stack.size());
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+}
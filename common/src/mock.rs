@@ -0,0 +1,306 @@
+//! A stand-in for the USB serial port, to run the firmware's read loops in host tests.
+use core::fmt;
+use embedded_io::{ErrorKind, ErrorType, Read, Write};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::vec::Vec;
+
+use crate::frame::{self, Kind, MAX_PAYLOAD};
+pub use crate::io::EOT;
+
+/// How newlines in queued data are sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// As they appear in the data
+    #[default]
+    Unchanged,
+    Lf,
+    CrLf,
+    Cr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockError {
+    /// No data available yet, the read should be retried
+    WouldBlock,
+    Other(ErrorKind),
+}
+
+impl embedded_io::Error for MockError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            // embedded-io has no WouldBlock, Interrupted has the same "try again" meaning
+            MockError::WouldBlock => ErrorKind::Interrupted,
+            MockError::Other(kind) => *kind,
+        }
+    }
+}
+
+impl fmt::Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug)]
+enum Event {
+    Data(VecDeque<u8>),
+    Error(MockError),
+}
+
+/// Replays queued input through `embedded_io::Read` and captures everything written
+#[derive(Debug, Default)]
+pub struct MockSerial {
+    events: VecDeque<Event>,
+    line_ending: LineEnding,
+    max_read: Option<usize>,
+    written: Vec<u8>,
+}
+
+impl MockSerial {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts the newlines of data queued after this call
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Returns at most `max` bytes per read, to simulate short reads
+    pub fn max_read(mut self, max: usize) -> Self {
+        assert!(max > 0, "A read of 0 bytes means end of file");
+        self.max_read = Some(max);
+        self
+    }
+
+    /// Queues bytes to be read
+    pub fn bytes(mut self, data: &[u8]) -> Self {
+        let data = convert_line_endings(data, self.line_ending);
+        self.push(data);
+        self
+    }
+
+    /// Queues data the way the host uploads it: in frames, followed by an end frame
+    pub fn upload(mut self, data: &[u8]) -> Self {
+        let data = Vec::from(convert_line_endings(data, self.line_ending));
+        let mut seq = 0u8;
+        for payload in data.chunks(MAX_PAYLOAD) {
+            self.push(
+                frame::encode(Kind::Data, seq, payload)
+                    .into_iter()
+                    .collect(),
+            );
+            seq = seq.wrapping_add(1);
+        }
+        self.push(frame::encode(Kind::End, seq, &[]).into_iter().collect());
+        self
+    }
+
+    /// Queues the contents of a file to be read
+    pub fn file(self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let data = std::fs::read(path)?;
+        Ok(self.bytes(&data))
+    }
+
+    /// Queues an End of Transmission
+    pub fn eot(self) -> Self {
+        self.bytes(&[EOT])
+    }
+
+    /// Makes the next read fail with WouldBlock, after the data queued so far
+    pub fn would_block(self) -> Self {
+        self.error(MockError::WouldBlock)
+    }
+
+    /// Makes the next read fail, after the data queued so far
+    pub fn error(mut self, error: MockError) -> Self {
+        self.events.push_back(Event::Error(error));
+        self
+    }
+
+    /// Whether all queued input has been read
+    pub fn is_drained(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Everything written so far
+    pub fn written(&self) -> &[u8] {
+        &self.written
+    }
+
+    /// Everything written so far, as text
+    pub fn written_str(&self) -> &str {
+        core::str::from_utf8(&self.written).expect("Written data is not UTF-8")
+    }
+
+    /// Clears the captured writes
+    pub fn clear_written(&mut self) {
+        self.written.clear();
+    }
+}
+
+impl MockSerial {
+    fn push(&mut self, data: VecDeque<u8>) {
+        match self.events.back_mut() {
+            Some(Event::Data(queued)) => queued.extend(data),
+            _ => self.events.push_back(Event::Data(data)),
+        }
+    }
+}
+
+fn convert_line_endings(data: &[u8], line_ending: LineEnding) -> VecDeque<u8> {
+    let newline: &[u8] = match line_ending {
+        LineEnding::Unchanged => return data.iter().copied().collect(),
+        LineEnding::Lf => b"\n",
+        LineEnding::CrLf => b"\r\n",
+        LineEnding::Cr => b"\r",
+    };
+
+    let mut res = VecDeque::with_capacity(data.len());
+    let mut bytes = data.iter().peekable();
+    while let Some(&b) = bytes.next() {
+        match b {
+            b'\r' if bytes.peek() == Some(&&b'\n') => {}
+            b'\r' | b'\n' => res.extend(newline),
+            b => res.push_back(b),
+        }
+    }
+    res
+}
+
+impl ErrorType for MockSerial {
+    type Error = MockError;
+}
+
+impl Read for MockSerial {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let Some(event) = self.events.front_mut() else {
+            return Ok(0);
+        };
+
+        let data = match event {
+            Event::Data(data) => data,
+            Event::Error(e) => {
+                let e = *e;
+                self.events.pop_front();
+                return Err(e);
+            }
+        };
+
+        let n = buf
+            .len()
+            .min(data.len())
+            .min(self.max_read.unwrap_or(usize::MAX));
+        for (dst, src) in buf.iter_mut().zip(data.drain(..n)) {
+            *dst = src;
+        }
+
+        if data.is_empty() {
+            self.events.pop_front();
+        }
+        Ok(n)
+    }
+}
+
+impl Write for MockSerial {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{EotReader, LineReader};
+
+    fn read_all(serial: &mut MockSerial) -> Vec<u8> {
+        let mut res = Vec::new();
+        let mut buf = [0u8; 16];
+        loop {
+            match serial.read(&mut buf) {
+                Ok(0) => return res,
+                Ok(n) => res.extend_from_slice(&buf[..n]),
+                Err(MockError::WouldBlock) => {}
+                Err(e) => panic!("{e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_replay_bytes() {
+        let mut serial = MockSerial::new().bytes(b"ab\n").bytes(b"c").eot();
+        assert_eq!(read_all(&mut serial), b"ab\nc\x04");
+        assert!(serial.is_drained());
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let mut serial = MockSerial::new()
+            .line_ending(LineEnding::CrLf)
+            .bytes(b"a\nb\r\nc\r")
+            .line_ending(LineEnding::Lf)
+            .bytes(b"d\r\n");
+        assert_eq!(read_all(&mut serial), b"a\r\nb\r\nc\r\nd\n");
+    }
+
+    #[test]
+    fn test_short_reads() {
+        let mut serial = MockSerial::new().max_read(2).bytes(b"hello");
+        let mut buf = [0u8; 8];
+        assert_eq!(serial.read(&mut buf), Ok(2));
+        assert_eq!(serial.read(&mut buf), Ok(2));
+        assert_eq!(serial.read(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"o");
+        assert_eq!(serial.read(&mut buf), Ok(0));
+    }
+
+    #[test]
+    fn test_would_block_between_data() {
+        let mut serial = MockSerial::new().bytes(b"a").would_block().bytes(b"b");
+        let mut buf = [0u8; 8];
+        assert_eq!(serial.read(&mut buf), Ok(1));
+        assert_eq!(serial.read(&mut buf), Err(MockError::WouldBlock));
+        assert_eq!(serial.read(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"b");
+    }
+
+    #[test]
+    fn test_capture_writes() {
+        let mut serial = MockSerial::new();
+        serial.write_all(b"<ACK 0>").unwrap();
+        serial.write_all(b"\n").unwrap();
+        assert_eq!(serial.written_str(), "<ACK 0>\n");
+        serial.clear_written();
+        assert!(serial.written().is_empty());
+    }
+
+    #[test]
+    fn test_line_reader_over_mock() {
+        let serial = MockSerial::new()
+            .line_ending(LineEnding::CrLf)
+            .max_read(1)
+            .bytes(b"Hello\n")
+            .would_block()
+            .bytes(b"World\n")
+            .eot();
+        let mut serial = EotReader::new(serial);
+        let mut lines = LineReader::<16>::new();
+
+        assert!(lines.read_until_newline(&mut serial).unwrap());
+        assert_eq!(lines.line(), b"Hello");
+
+        lines.clear();
+        assert!(lines.read_until_newline(&mut serial).unwrap());
+        assert_eq!(lines.line(), b"World");
+
+        lines.clear();
+        assert!(!lines.read_until_newline(&mut serial).unwrap());
+    }
+}
@@ -1,46 +1,456 @@
-/// Returns the number of digits in a given number
-pub fn count_digits(mut n: u64) -> u32 {
-    if n == 0 {
-        return 1;
-    }
-    let mut count = 0;
-    while n > 0 {
-        count += 1;
-        n /= 10;
-    }
-    count
-}
+//! Digits and number theory on integers.
+//!
+//! The digits of a `u64` can be taken in any [`Radix`], the decimal ones also with the
+//! free functions. The number theory is generic over the integer types with [`Int`].
+//! Both check every step for overflow, and return None when the answer doesn't exist
+//! or doesn't fit.
 
-/// Computes 10^n
-pub fn pow10(n: u32) -> u64 {
-    let mut result = 1;
-    let mut i = 0;
-    while i < n {
-        result *= 10;
+/// The powers of 10 that fit in a `u64`
+const POW10: [u64; 20] = {
+    let mut table = [1; 20];
+    let mut i = 1;
+    while i < 20 {
+        table[i] = table[i - 1] * 10;
         i += 1;
     }
-    result
+    table
+};
+
+/// Returns the number of digits in a given number
+pub fn count_digits(n: u64) -> u32 {
+    // The number of bits gives the number of digits to within one, as 1233 / 4096 is
+    // just under log10(2), and the table tells which
+    let n = n | 1;
+    let bits = u64::BITS - n.leading_zeros();
+    let guess = (bits * 1233) >> 12;
+    guess + (n >= POW10[guess as usize]) as u32
+}
+
+/// Computes 10^n, None if it doesn't fit
+pub fn pow10(n: u32) -> Option<u64> {
+    POW10.get(n as usize).copied()
 }
 
 /// Concatenates two numbers efficiently without using string operations
 #[inline]
 pub fn concat(a: u64, b: u64) -> Option<u64> {
-    let b_digits = count_digits(b);
-    let base = a.checked_mul(pow10(b_digits))?;
-    base.checked_add(b)
+    Radix::DECIMAL.concat(a, b)
 }
 
 /// Splits a number in 2 at index 'at'.
 pub fn split(num: u64, at: u32) -> (u64, u64) {
-    let factor = pow10(at);
-    let first_part = num / factor;
-    let last_part = num - first_part * factor;
-    (first_part, last_part)
+    Radix::DECIMAL.split(num, at)
+}
+
+/// The base numbers are written in, from 2 to 36
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Radix(u32);
+
+impl Radix {
+    pub const BINARY: Radix = Radix(2);
+    pub const OCTAL: Radix = Radix(8);
+    pub const DECIMAL: Radix = Radix(10);
+    pub const HEX: Radix = Radix(16);
+
+    /// None unless `radix` is in `2..=36`
+    pub const fn new(radix: u32) -> Option<Self> {
+        match radix {
+            2..=36 => Some(Radix(radix)),
+            _ => None,
+        }
+    }
+
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+
+    /// The number of digits of `n`, which is 1 for 0
+    pub fn count_digits(self, n: u64) -> u32 {
+        if self == Self::DECIMAL {
+            return count_digits(n);
+        }
+        let bits = u64::BITS - (n | 1).leading_zeros();
+        if self.0.is_power_of_two() {
+            return bits.div_ceil(self.0.trailing_zeros());
+        }
+        let mut count = 1;
+        let mut rest = n / self.0 as u64;
+        while rest > 0 {
+            count += 1;
+            rest /= self.0 as u64;
+        }
+        count
+    }
+
+    /// `radix ^ exp`, None if it doesn't fit
+    pub fn pow(self, exp: u32) -> Option<u64> {
+        match self == Self::DECIMAL {
+            true => pow10(exp),
+            false => (self.0 as u64).checked_pow(exp),
+        }
+    }
+
+    /// `radix ^ exp`, or `u64::MAX` if it doesn't fit
+    pub fn saturating_pow(self, exp: u32) -> u64 {
+        self.pow(exp).unwrap_or(u64::MAX)
+    }
+
+    /// The digits of `b` written after those of `a`, None if that doesn't fit
+    pub fn concat(self, a: u64, b: u64) -> Option<u64> {
+        a.checked_mul(self.pow(self.count_digits(b))?)?
+            .checked_add(b)
+    }
+
+    /// Like [`Radix::concat`], but `u64::MAX` if it doesn't fit
+    pub fn saturating_concat(self, a: u64, b: u64) -> u64 {
+        self.concat(a, b).unwrap_or(u64::MAX)
+    }
+
+    /// Splits `n` into the digits before the last `at` ones and those last ones
+    pub fn split(self, n: u64, at: u32) -> (u64, u64) {
+        match self.pow(at) {
+            Some(factor) => (n / factor, n % factor),
+            None => (0, n),
+        }
+    }
+
+    /// The digits of `n`, most significant first, or least significant first reversed
+    pub fn digits(self, n: u64) -> Digits {
+        Digits {
+            rest: n,
+            len: self.count_digits(n),
+            radix: self,
+        }
+    }
+
+    /// The number with the digits of `n` in reverse order, None if it doesn't fit.
+    /// Trailing zeros become leading ones, and are lost.
+    pub fn reverse(self, n: u64) -> Option<u64> {
+        self.from_digits(self.digits(n).rev())
+    }
+
+    /// The number with `digits`, most significant first, None if it doesn't fit or a
+    /// digit isn't below the radix
+    pub fn from_digits(self, digits: impl IntoIterator<Item = u32>) -> Option<u64> {
+        digits.into_iter().try_fold(0u64, |n, digit| {
+            (digit < self.0).then_some(())?;
+            n.checked_mul(self.0 as u64)?.checked_add(digit as u64)
+        })
+    }
+
+    /// Like [`Radix::from_digits`], but `u64::MAX` if it doesn't fit. Digits that
+    /// aren't below the radix still give None.
+    pub fn saturating_from_digits(self, digits: impl IntoIterator<Item = u32>) -> Option<u64> {
+        digits.into_iter().try_fold(0u64, |n, digit| {
+            (digit < self.0).then_some(())?;
+            Some(n.saturating_mul(self.0 as u64).saturating_add(digit as u64))
+        })
+    }
+}
+
+/// The digits of a number, see [`Radix::digits`]
+#[derive(Debug, Clone)]
+pub struct Digits {
+    /// The digits not yet taken from either end
+    rest: u64,
+    len: u32,
+    radix: Radix,
+}
+
+impl Iterator for Digits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.len = self.len.checked_sub(1)?;
+        // The remaining digits fit, so their place value does too
+        let place = self.radix.saturating_pow(self.len);
+        let digit = self.rest / place;
+        self.rest %= place;
+        Some(digit as u32)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u32> {
+        self.len = self.len.checked_sub(1)?;
+        let radix = self.radix.0 as u64;
+        let digit = self.rest % radix;
+        self.rest /= radix;
+        Some(digit as u32)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+/// The integer types the number theory works on
+pub trait Int: Copy + Ord {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    /// The remainder of division by `rhs`, between 0 and `|rhs|`
+    fn checked_rem_euclid(self, rhs: Self) -> Option<Self>;
+    fn checked_abs(self) -> Option<Self>;
+}
+
+/// The integer types with negative numbers
+pub trait SignedInt: Int {
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem_euclid(self, rhs)
+                }
+
+                #[allow(unused_comparisons)]
+                fn checked_abs(self) -> Option<Self> {
+                    match self < 0 {
+                        true => (0 as $t).checked_sub(self),
+                        false => Some(self),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! signed_int {
+    ($($t:ty),*) => {
+        $(
+            impl SignedInt for $t {
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+            }
+        )*
+    };
+}
+
+signed_int!(i8, i16, i32, i64, i128, isize);
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Int>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a.checked_abs()?, b.checked_abs()?);
+    while b != T::ZERO {
+        (a, b) = (b, a.checked_rem_euclid(b)?);
+    }
+    Some(a)
+}
+
+/// The least common multiple, which is never negative. It's 0 if either is 0.
+pub fn lcm<T: Int>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let g = gcd(a, b)?;
+    a.checked_abs()?
+        .checked_div(g)?
+        .checked_mul(b.checked_abs()?)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`
+pub fn extended_gcd<T: SignedInt>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0.checked_div(r1)?;
+        (r0, r1) = (r1, r0.checked_sub(q.checked_mul(r1)?)?);
+        (x0, x1) = (x1, x0.checked_sub(q.checked_mul(x1)?)?);
+        (y0, y1) = (y1, y0.checked_sub(q.checked_mul(y1)?)?);
+    }
+    if r0 < T::ZERO {
+        return Some((r0.checked_neg()?, x0.checked_neg()?, y0.checked_neg()?));
+    }
+    Some((r0, x0, y0))
+}
+
+/// `a * b mod m`, in `0..m`, even when `a * b` doesn't fit. None unless `m > 0`.
+pub fn mul_mod<T: Int>(a: T, b: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+    let (mut a, mut b) = (a.checked_rem_euclid(m)?, b.checked_rem_euclid(m)?);
+    if let Some(product) = a.checked_mul(b) {
+        return product.checked_rem_euclid(m);
+    }
+    // Double and add, every step stays below `m`
+    let mut result = T::ZERO;
+    while b != T::ZERO {
+        let two = T::ONE.checked_add(T::ONE)?;
+        if b.checked_rem_euclid(two)? == T::ONE {
+            result = add_mod(result, a, m)?;
+        }
+        a = add_mod(a, a, m)?;
+        b = b.checked_div(two)?;
+    }
+    Some(result)
+}
+
+/// `base ^ exp mod m`, in `0..m`. None unless `exp >= 0` and `m > 0`.
+pub fn mod_pow<T: Int>(base: T, exp: T, m: T) -> Option<T> {
+    if exp < T::ZERO || m <= T::ZERO {
+        return None;
+    }
+    let two = T::ONE.checked_add(T::ONE)?;
+    let (mut base, mut exp) = (base.checked_rem_euclid(m)?, exp);
+    let mut result = T::ONE.checked_rem_euclid(m)?;
+    while exp != T::ZERO {
+        if exp.checked_rem_euclid(two)? == T::ONE {
+            result = mul_mod(result, base, m)?;
+        }
+        base = mul_mod(base, base, m)?;
+        exp = exp.checked_div(two)?;
+    }
+    Some(result)
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, None if `a` and `m` share a factor or
+/// unless `m > 0`
+pub fn mod_inverse<T: Int>(a: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+    // Euclid's algorithm on (m, a), keeping only the coefficients of `a`, mod m
+    let (mut r0, mut r1) = (m, a.checked_rem_euclid(m)?);
+    let (mut t0, mut t1) = (T::ZERO, T::ONE.checked_rem_euclid(m)?);
+    while r1 != T::ZERO {
+        let q = r0.checked_div(r1)?;
+        (r0, r1) = (r1, r0.checked_sub(q.checked_mul(r1)?)?);
+        (t0, t1) = (t1, sub_mod(t0, mul_mod(q, t1, m)?, m)?);
+    }
+    (r0 == T::ONE).then_some(t0)
+}
+
+/// Combines congruences `x ≡ r (mod m)` into one `x ≡ r (mod lcm of the m)`, with
+/// the Chinese remainder theorem. The moduli needn't be coprime, but then the
+/// congruences may contradict each other, which gives None. So does any `m <= 0`.
+pub fn crt<T: Int>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut combined = (T::ZERO, T::ONE);
+    for &(r2, m2) in congruences {
+        let (r1, m1) = combined;
+        if m2 <= T::ZERO {
+            return None;
+        }
+        let r2 = r2.checked_rem_euclid(m2)?;
+        let g = gcd(m1, m2)?;
+        // x = r1 + m1 * k, with m1 * k ≡ r2 - r1 (mod m2)
+        let diff = sub_mod(r2, r1.checked_rem_euclid(m2)?, m2)?;
+        if diff.checked_rem_euclid(g)? != T::ZERO {
+            return None;
+        }
+        let m = m2.checked_div(g)?;
+        let inverse = mod_inverse(m1.checked_div(g)?, m)?;
+        let k = mul_mod(diff.checked_div(g)?, inverse, m)?;
+        let lcm = m1.checked_mul(m)?;
+        combined = (r1.checked_add(m1.checked_mul(k)?)?, lcm);
+    }
+    Some(combined)
+}
+
+/// `a + b mod m`, for `a` and `b` in `0..m`, without overflowing
+fn add_mod<T: Int>(a: T, b: T, m: T) -> Option<T> {
+    let room = m.checked_sub(b)?;
+    match a >= room {
+        true => a.checked_sub(room),
+        false => a.checked_add(b),
+    }
+}
+
+/// `a - b mod m`, for `a` and `b` in `0..m`, without overflowing
+fn sub_mod<T: Int>(a: T, b: T, m: T) -> Option<T> {
+    match a >= b {
+        true => a.checked_sub(b),
+        false => a.checked_add(m.checked_sub(b)?),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use heapless::Vec;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12u8, 18), Some(6));
+        assert_eq!(gcd(-12i32, 18), Some(6));
+        assert_eq!(gcd(0u64, 7), Some(7));
+        assert_eq!(gcd(0u64, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(lcm(4u16, 6), Some(12));
+        assert_eq!(lcm(-4i16, 6), Some(12));
+        assert_eq!(lcm(0u32, 6), Some(0));
+        assert_eq!(lcm(200u8, 3), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240i64, 46).unwrap();
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        let (g, x, y) = extended_gcd(-6i8, 4).unwrap();
+        assert_eq!((g, -6 * x + 4 * y), (2, 2));
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inverse(3u32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6u32, 9), None);
+        assert_eq!(mod_inverse(5u8, 0), None);
+        assert_eq!(mod_pow(2u64, 10, 1000), Some(24));
+        assert_eq!(mod_pow(7u8, 0, 1), Some(0));
+        assert_eq!(mod_pow(2i32, -1, 7), None);
+        // Products that overflow still come out right
+        assert_eq!(mul_mod(250u8, 250, 251), Some(1));
+        assert_eq!(mod_pow(u64::MAX - 1, u64::MAX - 1, u64::MAX), Some(1));
+        let p = (1u64 << 61) - 1;
+        assert_eq!(mod_inverse(p - 1, p), Some(p - 1));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2u32, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Robots back in formation every 101 and 103 seconds
+        assert_eq!(crt(&[(12i64, 101), (65, 103)]), Some((2537, 10403)));
+        // Not coprime, but consistent
+        assert_eq!(crt(&[(2u32, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1u32, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1u8, 16), (2, 17)]), None);
+        assert_eq!(crt::<u8>(&[]), Some((0, 1)));
+    }
 
     #[test]
     fn test_digit_counting() {
@@ -48,6 +458,53 @@ mod tests {
         assert_eq!(count_digits(1), 1);
         assert_eq!(count_digits(10), 2);
         assert_eq!(count_digits(100), 3);
+        for (i, &p) in POW10.iter().enumerate() {
+            assert_eq!(count_digits(p - 1), (i as u32).max(1));
+            assert_eq!(count_digits(p), i as u32 + 1);
+        }
+        assert_eq!(count_digits(u64::MAX), 20);
+
+        assert_eq!(Radix::BINARY.count_digits(0), 1);
+        assert_eq!(Radix::BINARY.count_digits(5), 3);
+        assert_eq!(Radix::OCTAL.count_digits(0o777), 3);
+        assert_eq!(Radix::OCTAL.count_digits(0o1000), 4);
+        assert_eq!(Radix::HEX.count_digits(u64::MAX), 16);
+        assert_eq!(Radix::new(3).unwrap().count_digits(27), 4);
+        assert_eq!(Radix::new(1), None);
+        assert_eq!(Radix::new(37), None);
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(pow10(0), Some(1));
+        assert_eq!(pow10(19), Some(10_000_000_000_000_000_000));
+        assert_eq!(pow10(20), None);
+        assert_eq!(Radix::BINARY.pow(63), Some(1 << 63));
+        assert_eq!(Radix::BINARY.pow(64), None);
+        assert_eq!(Radix::HEX.saturating_pow(16), u64::MAX);
+    }
+
+    #[test]
+    fn test_digits() {
+        let digits: Vec<u32, 20> = Radix::DECIMAL.digits(1203).collect();
+        assert_eq!(&digits[..], &[1, 2, 0, 3]);
+        let digits: Vec<u32, 20> = Radix::OCTAL.digits(0o2417).rev().collect();
+        assert_eq!(&digits[..], &[7, 1, 4, 2]);
+        let mut digits = Radix::DECIMAL.digits(12345);
+        assert_eq!((digits.next(), digits.next_back()), (Some(1), Some(5)));
+        assert_eq!(digits.len(), 3);
+        assert_eq!(digits.next_back(), Some(4));
+        assert_eq!(digits.next(), Some(2));
+        assert_eq!((digits.next(), digits.next()), (Some(3), None));
+        assert_eq!(Radix::DECIMAL.digits(u64::MAX).count(), 20);
+
+        assert_eq!(Radix::DECIMAL.reverse(1230), Some(321));
+        assert_eq!(Radix::BINARY.reverse(0b1101), Some(0b1011));
+        assert_eq!(Radix::DECIMAL.reverse(u64::MAX), None);
+        assert_eq!(Radix::OCTAL.from_digits([2, 4, 1, 7]), Some(0o2417));
+        assert_eq!(Radix::OCTAL.from_digits([8]), None);
+        assert_eq!(Radix::HEX.from_digits([15; 17]), None);
+        assert_eq!(Radix::HEX.saturating_from_digits([15; 17]), Some(u64::MAX));
     }
 
     #[test]
@@ -56,11 +513,16 @@ mod tests {
         assert_eq!(concat(1, 2), Some(12));
         assert_eq!(concat(0, 123), Some(123));
         assert_eq!(concat(123, 0), Some(1230));
+        assert_eq!(concat(2, 10_000_000_000_000_000_000), None);
+        assert_eq!(Radix::BINARY.concat(0b10, 0b11), Some(0b1011));
+        assert_eq!(Radix::DECIMAL.saturating_concat(u64::MAX, 1), u64::MAX);
     }
 
     #[test]
     fn test_split() {
         assert_eq!(split(123456, 3), (123, 456));
         assert_eq!(split(4444, 2), (44, 44));
+        assert_eq!(split(u64::MAX, 25), (0, u64::MAX));
+        assert_eq!(Radix::HEX.split(0xabcd, 1), (0xabc, 0xd));
     }
 }
@@ -0,0 +1,193 @@
+//! Machine-readable answers, one JSON object per line (JSON Lines).
+//!
+//! ```text
+//! {"day":13,"hz":16000000,"parse_cycles":51234}
+//! {"day":13,"part":2,"value":480,"cycles":1234,"peak_mem":null}
+//! ```
+//!
+//! Text answers are strings and unsolved parts are null, as are the measurements that
+//! weren't taken. When the run is timed, a [`Timing`] record precedes the answers.
+//! After them, a [`UsageRecord`] per fixed-capacity container shows how full it got:
+//!
+//! ```text
+//! {"day":6,"usage":"path","peak":41,"capacity":8192}
+//! ```
+use core::fmt::{self, Display, Write};
+
+use crate::memory::Usage;
+use crate::solution::Answer;
+
+/// The answer to one part, with what it took to compute it
+#[derive(Debug, Clone, Copy)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub value: &'a Answer,
+    /// Ticks of the run's clock spent computing the answer, see [`Timing`]
+    pub cycles: Option<u64>,
+    /// The most stack in use while computing the answer, in bytes.
+    /// This includes the solution's state, which lives on the stack too.
+    pub peak_mem: Option<usize>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(day: u8, part: u8, value: &'a Answer) -> Self {
+        Self {
+            day,
+            part,
+            value,
+            cycles: None,
+            peak_mem: None,
+        }
+    }
+}
+
+impl Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{\"day\":{},\"part\":{},\"value\":",
+            self.day, self.part
+        )?;
+        match self.value {
+            Answer::Unsolved => f.write_str("null")?,
+            Answer::Number(n) => write!(f, "{n}")?,
+            Answer::Text(s) => write_str(f, s)?,
+        }
+        f.write_str(",\"cycles\":")?;
+        write_option(f, self.cycles)?;
+        f.write_str(",\"peak_mem\":")?;
+        write_option(f, self.peak_mem)?;
+        f.write_char('}')
+    }
+}
+
+/// The clock a run was timed with, and how long receiving and parsing the input took
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u8,
+    /// How many cycles make a second
+    pub hz: u64,
+    pub parse_cycles: u64,
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{\"day\":{},\"hz\":{},\"parse_cycles\":{}}}",
+            self.day, self.hz, self.parse_cycles
+        )
+    }
+}
+
+/// How full a container got, the stack is reported as `stack`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsageRecord {
+    pub day: u8,
+    pub usage: Usage,
+}
+
+impl Display for UsageRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"day\":{},\"usage\":", self.day)?;
+        write_str(f, self.usage.name)?;
+        write!(
+            f,
+            ",\"peak\":{},\"capacity\":{}}}",
+            self.usage.peak, self.usage.capacity
+        )
+    }
+}
+
+fn write_option(f: &mut fmt::Formatter<'_>, value: Option<impl Display>) -> fmt::Result {
+    match value {
+        Some(value) => write!(f, "{value}"),
+        None => f.write_str("null"),
+    }
+}
+
+/// Writes a JSON string literal
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    fn json(record: Record) -> String<128> {
+        let mut s = String::new();
+        write!(s, "{record}").unwrap();
+        s
+    }
+
+    #[test]
+    fn test_number() {
+        let value = Answer::Number(480);
+        let record = Record {
+            cycles: Some(1234),
+            ..Record::new(13, 2, &value)
+        };
+        assert_eq!(
+            json(record),
+            r#"{"day":13,"part":2,"value":480,"cycles":1234,"peak_mem":null}"#
+        );
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        let value = Answer::Text(String::try_from("4,6 \"a\\b\"\t").unwrap());
+        assert_eq!(
+            json(Record::new(17, 1, &value)),
+            r#"{"day":17,"part":1,"value":"4,6 \"a\\b\"\u0009","cycles":null,"peak_mem":null}"#
+        );
+    }
+
+    #[test]
+    fn test_timing() {
+        let timing = Timing {
+            day: 13,
+            hz: 16_000_000,
+            parse_cycles: 51234,
+        };
+        let mut s: String<128> = String::new();
+        write!(s, "{timing}").unwrap();
+        assert_eq!(s, r#"{"day":13,"hz":16000000,"parse_cycles":51234}"#);
+    }
+
+    #[test]
+    fn test_usage() {
+        let usage = Usage {
+            name: "path",
+            peak: 41,
+            capacity: 8192,
+        };
+        let mut s: String<128> = String::new();
+        write!(s, "{}", UsageRecord { day: 6, usage }).unwrap();
+        assert_eq!(s, r#"{"day":6,"usage":"path","peak":41,"capacity":8192}"#);
+    }
+
+    #[test]
+    fn test_unsolved() {
+        let record = Record {
+            peak_mem: Some(2048),
+            ..Record::new(9, 1, &Answer::Unsolved)
+        };
+        assert_eq!(
+            json(record),
+            r#"{"day":9,"part":1,"value":null,"cycles":null,"peak_mem":2048}"#
+        );
+    }
+}
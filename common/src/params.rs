@@ -0,0 +1,243 @@
+//! Tunables a day declares, like the number of blinks or the size of the room, and the
+//! values a run gives them.
+//!
+//! A run gives its values on the header line, as `name=value` words after the day and
+//! part, e.g. `DAY 14 PART 1 width=11 height=7`. The tunables a run leaves out keep the
+//! default the day declared. Values are integers, checked against the range the day
+//! declared before any input is parsed, so a day can narrow them without checking again.
+use core::fmt::{self, Display};
+use heapless::{String, Vec};
+
+/// The most values a run can give
+pub const MAX_PARAMS: usize = 4;
+/// The longest name a tunable can have
+pub const NAME_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Not a `name=value` word, or the name is too long
+    Invalid,
+    /// More values than [`MAX_PARAMS`]
+    TooMany,
+    /// The day doesn't declare a tunable by this name
+    Unknown(String<NAME_LENGTH>),
+    /// The value is outside of the declared range
+    OutOfRange(&'static Param),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Invalid => f.write_str("Invalid parameter, expected e.g. width=11"),
+            Error::TooMany => write!(f, "More than {MAX_PARAMS} parameters"),
+            Error::Unknown(name) => write!(f, "Unknown parameter {name}"),
+            Error::OutOfRange(param) => write!(
+                f,
+                "Parameter {} must be within {}..={}",
+                param.name, param.min, param.max
+            ),
+        }
+    }
+}
+
+/// A tunable of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub max: i64,
+    /// What it tunes
+    pub help: &'static str,
+}
+
+/// The values a run gives, in the order they were given
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Values {
+    values: Vec<(String<NAME_LENGTH>, i64), MAX_PARAMS>,
+}
+
+impl Values {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|&(_, value)| value)
+    }
+
+    /// Gives `name` the value, replacing the one it was given before
+    pub fn set(&mut self, name: &str, value: i64) -> Result<(), Error> {
+        if let Some(slot) = self.values.iter_mut().find(|(n, _)| n == name) {
+            slot.1 = value;
+            return Ok(());
+        }
+        let name = String::try_from(name).map_err(|_| Error::Invalid)?;
+        self.values.push((name, value)).map_err(|_| Error::TooMany)
+    }
+
+    /// Sets a value from a `name=value` word
+    pub fn set_word(&mut self, word: &str) -> Result<(), Error> {
+        let (name, value) = word.split_once('=').ok_or(Error::Invalid)?;
+        if name.is_empty() {
+            return Err(Error::Invalid);
+        }
+        let value = value.parse().map_err(|_| Error::Invalid)?;
+        self.set(name, value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+/// The values as `name=value` words, separated by spaces
+impl Display for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// What a day is tuned with for one run: the values the run gave, or else the defaults
+/// the day declared, and the parts the run reports
+#[derive(Debug, Clone, Copy)]
+pub struct Params<'a> {
+    declared: &'static [Param],
+    values: &'a Values,
+    part: Option<u8>,
+}
+
+impl<'a> Params<'a> {
+    /// Checks the given values against the tunables the day declared
+    pub fn new(
+        declared: &'static [Param],
+        values: &'a Values,
+        part: Option<u8>,
+    ) -> Result<Self, Error> {
+        for (name, value) in values.iter() {
+            let Some(param) = declared.iter().find(|p| p.name == name) else {
+                return Err(Error::Unknown(String::try_from(name).unwrap_or_default()));
+            };
+            if !(param.min..=param.max).contains(&value) {
+                return Err(Error::OutOfRange(param));
+            }
+        }
+        Ok(Self {
+            declared,
+            values,
+            part,
+        })
+    }
+
+    /// The value of a tunable, converted to the type the day works with.
+    /// Panics if the day didn't declare it, or if its range doesn't fit the type.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let Some(param) = self.declared.iter().find(|p| p.name == name) else {
+            panic!("Parameter {name} isn't declared");
+        };
+        let value = self.values.get(name).unwrap_or(param.default);
+        match T::try_from(value) {
+            Ok(value) => value,
+            Err(_) => panic!("Parameter {name} doesn't fit its type"),
+        }
+    }
+
+    /// Whether the run reports the given part.
+    /// Part 1 is always computed, as part 2 may build on it, but needn't do any work.
+    pub fn wants(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    const DECLARED: &[Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            min: 1,
+            max: 101,
+            help: "The width of the room",
+        },
+        Param {
+            name: "seconds",
+            default: 100,
+            min: 0,
+            max: 100_000,
+            help: "How long the robots walk",
+        },
+    ];
+
+    #[test]
+    fn test_values() {
+        let mut values = Values::new();
+        values.set_word("width=11").unwrap();
+        values.set_word("seconds=-3").unwrap();
+        values.set_word("width=7").unwrap();
+        assert_eq!(values.get("width"), Some(7));
+        assert_eq!(values.get("height"), None);
+
+        let mut s: String<32> = String::new();
+        write!(s, "{values}").unwrap();
+        assert_eq!(s, "width=7 seconds=-3");
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let mut values = Values::new();
+        assert_eq!(values.set_word("width"), Err(Error::Invalid));
+        assert_eq!(values.set_word("=3"), Err(Error::Invalid));
+        assert_eq!(values.set_word("width=x"), Err(Error::Invalid));
+        assert_eq!(values.set_word("a_very_long_name=1"), Err(Error::Invalid));
+        for name in ["a", "b", "c", "d"] {
+            values.set(name, 1).unwrap();
+        }
+        assert_eq!(values.set("e", 1), Err(Error::TooMany));
+    }
+
+    #[test]
+    fn test_params() {
+        let mut values = Values::new();
+        values.set("width", 11).unwrap();
+        let params = Params::new(DECLARED, &values, Some(1)).unwrap();
+        assert_eq!(params.get::<i16>("width"), 11);
+        assert_eq!(params.get::<u32>("seconds"), 100);
+        assert!(params.wants(1));
+        assert!(!params.wants(2));
+    }
+
+    #[test]
+    fn test_params_checked() {
+        let mut values = Values::new();
+        values.set("width", 102).unwrap();
+        assert_eq!(
+            Params::new(DECLARED, &values, None).err(),
+            Some(Error::OutOfRange(&DECLARED[0]))
+        );
+
+        let mut values = Values::new();
+        values.set("height", 7).unwrap();
+        assert_eq!(
+            Params::new(DECLARED, &values, None).err(),
+            Some(Error::Unknown(String::try_from("height").unwrap()))
+        );
+    }
+}
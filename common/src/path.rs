@@ -0,0 +1,577 @@
+//! Shortest paths through a graph of up to `N` states, in fixed-capacity storage.
+//!
+//! The states are whatever the day needs, e.g. a position or a position and a
+//! direction. The day numbers them with an `index` function into `0..N` so what's known
+//! about each state can be kept in arrays, and describes the graph with a `neighbours`
+//! function that reports the states one step away, with the step's cost for the
+//! weighted searches.
+//!
+//! A search fills in the distance field: the distance of every state it reached. All
+//! that's kept per state is one [`Slot`], so the field of a big graph still fits on the
+//! device. Predecessors aren't stored but found again from the field: a neighbour is a
+//! predecessor on a shortest path when its distance plus the step is the state's own,
+//! see [`Search::predecessors`] and [`Search::on_shortest_paths`]. That's on purpose:
+//! a set of predecessors per state would take several times the memory of the field,
+//! for something only needed while walking back over it once.
+//!
+//! Every search waits on the same priority queue, so a `Search` only holds one queue,
+//! whichever searches it's used for.
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+use core::ops::{Add, Sub};
+use heapless::binary_heap::{BinaryHeap, Min};
+
+/// The types a distance can be counted in
+pub trait Cost: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+}
+
+macro_rules! cost {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MAX: Self = <$t>::MAX;
+            }
+        )*
+    };
+}
+
+cost!(u8, u16, u32, u64, usize);
+
+/// What's known about a state during a search: whether it was reached, whether its
+/// distance is final, and the distance, packed into a single integer.
+///
+/// The top 2 bits are the flags, the rest holds the distance. Packed into the same
+/// type, a distance must fit in the remaining bits. Packed into a `u16` for `u32`
+/// costs, only the distance's lowest 14 bits are kept, and it's recovered from a
+/// distance within 8191 of it. That's enough as long as neighbouring states are never
+/// more than 8191 apart, in either direction.
+pub trait Slot<C>: Copy {
+    /// A state that wasn't reached
+    const UNREACHED: Self;
+
+    /// A state reached at distance `cost`, which isn't final yet
+    fn reached(cost: C) -> Self;
+
+    fn is_reached(self) -> bool;
+
+    fn is_done(self) -> bool;
+
+    fn set_done(&mut self, done: bool);
+
+    /// The distance, given any distance `near` that's close enough to it
+    fn cost(self, near: C) -> C;
+}
+
+macro_rules! slot {
+    ($($t:ty),*) => {
+        $(
+            impl Slot<$t> for $t {
+                const UNREACHED: Self = 0;
+
+                fn reached(cost: $t) -> Self {
+                    debug_assert!(cost <= Self::MAX >> 2, "{cost} doesn't fit in a slot");
+                    cost | 1 << (<$t>::BITS - 1)
+                }
+
+                fn is_reached(self) -> bool {
+                    self >> (<$t>::BITS - 1) == 1
+                }
+
+                fn is_done(self) -> bool {
+                    self >> (<$t>::BITS - 2) & 1 == 1
+                }
+
+                fn set_done(&mut self, done: bool) {
+                    let flag = 1 << (<$t>::BITS - 2);
+                    *self = if done { *self | flag } else { *self & !flag };
+                }
+
+                fn cost(self, _near: $t) -> $t {
+                    self & Self::MAX >> 2
+                }
+            }
+        )*
+    };
+}
+
+slot!(u8, u16, u32, u64, usize);
+
+/// The distance bits of a `u16` slot that wraps
+const WRAP_MASK: u16 = u16::MAX >> 2;
+
+impl Slot<u32> for u16 {
+    const UNREACHED: Self = 0;
+
+    fn reached(cost: u32) -> Self {
+        (cost as u16 & WRAP_MASK) | 1 << 15
+    }
+
+    fn is_reached(self) -> bool {
+        <u16 as Slot<u16>>::is_reached(self)
+    }
+
+    fn is_done(self) -> bool {
+        <u16 as Slot<u16>>::is_done(self)
+    }
+
+    fn set_done(&mut self, done: bool) {
+        <u16 as Slot<u16>>::set_done(self, done)
+    }
+
+    fn cost(self, near: u32) -> u32 {
+        // How far the distance is from `near`, as a signed 14 bit number
+        let diff = (self & WRAP_MASK).wrapping_sub(near as u16) & WRAP_MASK;
+        let diff = ((diff << 2) as i16 >> 2) as i32;
+        near.wrapping_add_signed(diff)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// More states were waiting to be visited than the queue holds
+    QueueFull,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::QueueFull => f.write_str("The search queue is full"),
+        }
+    }
+}
+
+/// A state waiting to be visited, the one with the lowest priority goes first
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+/// The storage for searches through up to `N` states with distances in `C`, kept in
+/// slots of type `D`. Up to `Q` states can wait to be visited.
+/// Each search starts afresh, so one `Search` can be reused for many.
+pub struct Search<S, C, const N: usize, const Q: usize, D = C> {
+    slots: [D; N],
+    queue: BinaryHeap<Entry<S, C>, Min, Q>,
+}
+
+impl<S, C, const N: usize, const Q: usize, D> Default for Search<S, C, N, Q, D>
+where
+    S: Copy,
+    C: Cost,
+    D: Slot<C>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, C: Cost, const N: usize, const Q: usize> Search<S, C, N, Q, C>
+where
+    S: Copy,
+    C: Slot<C>,
+{
+    /// The distance from the nearest start to the state at `index`, None if the last
+    /// search didn't reach it
+    pub fn distance(&self, index: usize) -> Option<C> {
+        self.distance_near(index, C::ZERO)
+    }
+}
+
+impl<S, C, const N: usize, const Q: usize, D> Search<S, C, N, Q, D>
+where
+    S: Copy,
+    C: Cost,
+    D: Slot<C>,
+{
+    pub fn new() -> Self {
+        Self {
+            slots: [D::UNREACHED; N],
+            queue: BinaryHeap::new(),
+        }
+    }
+
+    /// The distance to the state at `index` like [`Search::distance`], for slots that
+    /// need a distance `near` it to recover it, see [`Slot`]
+    pub fn distance_near(&self, index: usize, near: C) -> Option<C> {
+        let slot = self.slots[index];
+        slot.is_reached().then(|| slot.cost(near))
+    }
+
+    /// Finds the shortest paths from `starts` with Dijkstra's algorithm, until every
+    /// state as close as the nearest goal has been visited. Returns the nearest goal and
+    /// its distance, or None if no goal could be reached, in which case every reachable
+    /// state has been visited.
+    pub fn dijkstra(
+        &mut self,
+        starts: &[S],
+        index: impl Fn(&S) -> usize,
+        neighbours: impl FnMut(&S, &mut dyn FnMut(S, C)),
+        is_goal: impl FnMut(&S) -> bool,
+    ) -> Result<Option<(S, C)>, Error> {
+        self.astar(starts, index, neighbours, |_| C::ZERO, is_goal)
+    }
+
+    /// Finds the shortest paths from `starts` like [`Search::dijkstra`], visiting the
+    /// states that look closer to a goal first. The `heuristic` estimates the distance
+    /// from a state to the nearest goal. It must be consistent: never more than the cost
+    /// of a step plus the estimate from where the step leads, and 0 at a goal. States
+    /// aren't revisited once they're done, so a heuristic that's merely admissible can
+    /// finish a state at more than its distance.
+    pub fn astar(
+        &mut self,
+        starts: &[S],
+        index: impl Fn(&S) -> usize,
+        mut neighbours: impl FnMut(&S, &mut dyn FnMut(S, C)),
+        mut heuristic: impl FnMut(&S) -> C,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Result<Option<(S, C)>, Error> {
+        self.reset();
+        for &start in starts {
+            self.slots[index(&start)] = D::reached(C::ZERO);
+            self.push(heuristic(&start), C::ZERO, start)?;
+        }
+
+        let mut goal = None;
+        while let Some(Entry {
+            priority,
+            cost,
+            state,
+        }) = self.queue.pop()
+        {
+            // Keep going until nothing left could be as close as the goal, so every
+            // state on a shortest path to a goal at that distance is final
+            if goal.is_some_and(|(_, best)| priority > best) {
+                break;
+            }
+            let slot = &mut self.slots[index(&state)];
+            if slot.is_done() {
+                continue;
+            }
+            slot.set_done(true);
+            if goal.is_none() && is_goal(&state) {
+                goal = Some((state, cost));
+            }
+
+            let mut result = Ok(());
+            neighbours(&state, &mut |next, step| {
+                let next_cost = cost + step;
+                let slot = &mut self.slots[index(&next)];
+                if result.is_err()
+                    || slot.is_done()
+                    || slot.is_reached() && slot.cost(next_cost) <= next_cost
+                {
+                    return;
+                }
+                *slot = D::reached(next_cost);
+                result = self.push(next_cost + heuristic(&next), next_cost, next);
+            });
+            result?;
+        }
+        Ok(goal)
+    }
+
+    /// Finds the shortest paths from `starts` when every step costs the same, visiting
+    /// the states nearest to the starts first. A state's distance is final as soon as
+    /// it's reached, so this stops at the first goal it visits, unlike
+    /// [`Search::dijkstra`].
+    pub fn bfs(
+        &mut self,
+        starts: &[S],
+        index: impl Fn(&S) -> usize,
+        mut neighbours: impl FnMut(&S, &mut dyn FnMut(S)),
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Result<Option<(S, C)>, Error> {
+        self.reset();
+        for &start in starts {
+            self.slots[index(&start)] = D::reached(C::ZERO);
+            self.push(C::ZERO, C::ZERO, start)?;
+        }
+
+        while let Some(Entry { cost, state, .. }) = self.queue.pop() {
+            self.slots[index(&state)].set_done(true);
+            if is_goal(&state) {
+                return Ok(Some((state, cost)));
+            }
+
+            let mut result = Ok(());
+            neighbours(&state, &mut |next| {
+                let slot = &mut self.slots[index(&next)];
+                if result.is_err() || slot.is_reached() {
+                    return;
+                }
+                *slot = D::reached(cost + C::ONE);
+                result = self.push(cost + C::ONE, cost + C::ONE, next);
+            });
+            result?;
+        }
+        Ok(None)
+    }
+
+    /// Calls `f` with every predecessor of `state` on a shortest path to it, `cost` away
+    /// from the start. The `candidates` are the states `state` can be reached from in one
+    /// step, and the step's cost, like `neighbours` in reverse.
+    pub fn predecessors(
+        &self,
+        state: &S,
+        cost: C,
+        index: impl Fn(&S) -> usize,
+        mut candidates: impl FnMut(&S, &mut dyn FnMut(S, C)),
+        mut f: impl FnMut(S),
+    ) {
+        candidates(state, &mut |prev, step| {
+            let slot = self.slots[index(&prev)];
+            // Only final distances count, anything closer than a goal is final
+            if slot.is_done() && step <= cost && slot.cost(cost) == cost - step {
+                f(prev);
+            }
+        });
+    }
+
+    /// Calls `f` once for every state on any shortest path to the nearest of `ends`,
+    /// from the ends back to the starts, with its distance. The `candidates` are like
+    /// in [`Search::predecessors`]. This uses up what the search knows about the states
+    /// it passes, search again before asking about them.
+    pub fn on_shortest_paths(
+        &mut self,
+        ends: &[S],
+        index: impl Fn(&S) -> usize,
+        mut candidates: impl FnMut(&S, &mut dyn FnMut(S, C)),
+        mut f: impl FnMut(&S, C),
+    ) -> Result<(), Error> {
+        let reached = ends
+            .iter()
+            .map(|end| self.slots[index(end)])
+            .filter(|slot| slot.is_reached());
+        // All ends are close enough to each other to recover each other's distance
+        let Some(near) = reached.clone().next().map(|slot| slot.cost(C::ZERO)) else {
+            return Ok(());
+        };
+        let Some(nearest) = reached.map(|slot| slot.cost(near)).min() else {
+            return Ok(());
+        };
+
+        // Reuse the queue to walk back, the farthest states first so it stays small.
+        // A state leaves the walk by being marked not done.
+        self.queue.clear();
+        for (i, &end) in ends.iter().enumerate() {
+            let slot = self.slots[index(&end)];
+            let is_first = !ends[..i].iter().any(|e| index(e) == index(&end));
+            if slot.is_reached() && slot.cost(nearest) == nearest && is_first {
+                self.push(C::MAX - nearest, nearest, end)?;
+            }
+        }
+        while let Some(Entry { cost, state, .. }) = self.queue.pop() {
+            f(&state, cost);
+
+            let mut result = Ok(());
+            candidates(&state, &mut |prev, step| {
+                let slot = &mut self.slots[index(&prev)];
+                if result.is_err()
+                    || !slot.is_done()
+                    || step > cost
+                    || slot.cost(cost) != cost - step
+                {
+                    return;
+                }
+                slot.set_done(false);
+                result = self.push(C::MAX - (cost - step), cost - step, prev);
+            });
+            result?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.slots.fill(D::UNREACHED);
+        self.queue.clear();
+    }
+
+    fn push(&mut self, priority: C, cost: C, state: S) -> Result<(), Error> {
+        self.queue
+            .push(Entry {
+                priority,
+                cost,
+                state,
+            })
+            .map_err(|_| Error::QueueFull)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Point;
+    use crate::grid::{Grid, Pos};
+    use heapless::Vec;
+
+    /// Two equally long ways around the wall in the middle
+    const MAZE: &[&[u8]] = &[b"S...", b".##.", b"...E"];
+
+    type Maze = Grid<bool, 4, 3>;
+
+    fn maze() -> Maze {
+        let mut maze = Maze::default();
+        for row in MAZE {
+            maze.push_row(row, |b| b != b'#').unwrap();
+        }
+        maze
+    }
+
+    fn index(pos: &Pos) -> usize {
+        pos.y as usize * 4 + pos.x as usize
+    }
+
+    /// Every step costs 1, so the maze reads the same in reverse
+    fn open(maze: &Maze) -> impl Fn(&Pos, &mut dyn FnMut(Pos, u32)) + '_ {
+        move |pos, f| {
+            for (next, &open) in maze.neighbours4(*pos) {
+                if open {
+                    f(next, 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_slots() {
+        let mut slot = <u16 as Slot<u16>>::reached(1234);
+        assert!(Slot::<u16>::is_reached(slot) && !Slot::<u16>::is_done(slot));
+        Slot::<u16>::set_done(&mut slot, true);
+        assert!(Slot::<u16>::is_done(slot));
+        assert_eq!(Slot::<u16>::cost(slot, 0), 1234);
+        assert!(!Slot::<u16>::is_reached(<u16 as Slot<u16>>::UNREACHED));
+
+        // Wrapping slots recover distances far beyond what fits in them
+        let mut slot = <u16 as Slot<u32>>::reached(100_000);
+        Slot::<u32>::set_done(&mut slot, true);
+        assert_eq!(Slot::<u32>::cost(slot, 100_000 - 8191), 100_000);
+        assert_eq!(Slot::<u32>::cost(slot, 100_000 + 8191), 100_000);
+        assert_eq!(Slot::<u32>::cost(slot, 100_000 - 8192), 100_000 - 16384);
+        let slot = <u16 as Slot<u32>>::reached(5);
+        assert_eq!(Slot::<u32>::cost(slot, 0), 5);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let maze = maze();
+        let end = Point::new(3, 2);
+        let mut search: Search<Pos, u32, 12, 16> = Search::new();
+
+        let found = search.dijkstra(&[Pos::default()], index, open(&maze), |&p| p == end);
+        assert_eq!(found, Ok(Some((end, 5))));
+        assert_eq!(search.distance(index(&Point::new(3, 0))), Some(3));
+        assert_eq!(search.distance(index(&Point::new(1, 1))), None);
+
+        let mut prev: Vec<Pos, 4> = Vec::new();
+        search.predecessors(&end, 5, index, open(&maze), |p| prev.push(p).unwrap());
+        assert_eq!(&prev[..], &[Point::new(3, 1), Point::new(2, 2)]);
+
+        let mut tiles = 0;
+        search
+            .on_shortest_paths(&[end], index, open(&maze), |_, _| tiles += 1)
+            .unwrap();
+        assert_eq!(tiles, 10);
+    }
+
+    #[test]
+    fn test_weighted_wrapping() {
+        // Stepping right costs so much the distances outgrow the slots
+        let maze = maze();
+        let end = Point::new(3, 2);
+        let cost = |pos: &Pos, next: &Pos| if next.x > pos.x { 5000 } else { 1 };
+        let neighbours = |pos: &Pos, f: &mut dyn FnMut(Pos, u32)| {
+            for (next, &open) in maze.neighbours4(*pos) {
+                if open {
+                    f(next, cost(pos, &next));
+                }
+            }
+        };
+        let candidates = |pos: &Pos, f: &mut dyn FnMut(Pos, u32)| {
+            for (prev, &open) in maze.neighbours4(*pos) {
+                if open {
+                    f(prev, cost(&prev, pos));
+                }
+            }
+        };
+
+        let mut search: Search<Pos, u32, 12, 16, u16> = Search::new();
+        let found = search.dijkstra(&[Pos::default()], index, neighbours, |&p| p == end);
+        assert_eq!(found, Ok(Some((end, 15002))));
+        let top_right = index(&Point::new(3, 0));
+        assert_eq!(search.distance_near(top_right, 15002), Some(15000));
+
+        let mut tiles = 0;
+        search
+            .on_shortest_paths(&[end], index, candidates, |_, _| tiles += 1)
+            .unwrap();
+        assert_eq!(tiles, 10);
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = maze();
+        let end = Point::new(3, 2);
+        let mut search: Search<Pos, u32, 12, 16> = Search::new();
+
+        let found = search.astar(
+            &[Pos::default()],
+            index,
+            open(&maze),
+            |p| p.manhattan(end) as u32,
+            |&p| p == end,
+        );
+        assert_eq!(found, Ok(Some((end, 5))));
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let mut search: Search<Pos, u16, 12, 16> = Search::new();
+        let neighbours = open(&maze);
+
+        // Without a goal, the whole maze is explored
+        let found = search.bfs(
+            &[Pos::default()],
+            index,
+            |pos, f| neighbours(pos, &mut |next, _| f(next)),
+            |_| false,
+        );
+        assert_eq!(found, Ok(None));
+        assert_eq!(search.distance(index(&Point::new(3, 2))), Some(5));
+        assert_eq!(search.distance(index(&Point::new(2, 2))), Some(4));
+    }
+
+    #[test]
+    fn test_queue_full() {
+        let maze = maze();
+        let mut search: Search<Pos, u32, 12, 1> = Search::new();
+        let found = search.dijkstra(&[Pos::default()], index, open(&maze), |_| false);
+        assert_eq!(found, Err(Error::QueueFull));
+    }
+}
@@ -0,0 +1,321 @@
+//! Connected regions of a [`Grid`], for the days that flood a map.
+//!
+//! The fill keeps the cells still to visit in a fixed-capacity queue instead of
+//! recursing, so a big region fails with [`Error::QueueFull`] instead of overflowing
+//! the device's stack. It visits the cells breadth first, so the queue only holds the
+//! edge of what was filled so far, e.g. a diagonal for a solid square, rather than most
+//! of the region. Which cells are connected is up to the day: a step from one cell
+//! to a neighbour is taken when `connected(from, to)` holds. That needn't be symmetric,
+//! e.g. only stepping uphill, but the statistics of a [`Region`] are only meaningful
+//! when it is, like cells with the same plant.
+use core::fmt::{self, Display};
+use heapless::Deque;
+
+use crate::geom::{Dir4, Dir8};
+use crate::grid::{Grid, Pos};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// More cells were waiting to be visited than the queue holds
+    QueueFull,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::QueueFull => f.write_str("The flood fill queue is full"),
+        }
+    }
+}
+
+/// The shape of a region that was filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// The cell the fill started from
+    pub start: Pos,
+    /// The number of cells
+    pub area: usize,
+    /// The number of cell edges between the region and the rest of the grid
+    pub perimeter: usize,
+    /// The number of corners of the outline, inner ones and those of holes included
+    pub corners: usize,
+    /// The top left corner of the bounding box
+    pub min: Pos,
+    /// The bottom right corner of the bounding box, inclusive
+    pub max: Pos,
+}
+
+impl Region {
+    /// The number of straight sides of the outline, which has as many sides as corners
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+}
+
+/// The storage for flood fills of a `width` by `height` grid of up to `W` by `H`
+/// cells, with up to `Q` cells waiting to be visited. It remembers which cells were
+/// filled, so every cell is filled at most once until it's cleared.
+pub struct Regions<const W: usize, const H: usize, const Q: usize> {
+    filled: Grid<bool, W, H>,
+    queue: Deque<Pos, Q>,
+}
+
+impl<const W: usize, const H: usize, const Q: usize> Regions<W, H, Q> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            filled: Grid::filled(width, height, false),
+            queue: Deque::new(),
+        }
+    }
+
+    /// Storage for flood fills of `grid`
+    pub fn of<T>(grid: &Grid<T, W, H>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    /// Forgets which cells were filled
+    pub fn clear(&mut self) {
+        self.filled = Grid::filled(self.filled.width(), self.filled.height(), false);
+    }
+
+    pub fn is_filled(&self, pos: Pos) -> bool {
+        self.filled.get(pos).is_some_and(|&filled| filled)
+    }
+
+    /// Fills every cell that can be reached from `start` and wasn't filled before,
+    /// calling `f` with each. Returns how many cells were filled.
+    pub fn fill<T>(
+        &mut self,
+        grid: &Grid<T, W, H>,
+        start: Pos,
+        mut connected: impl FnMut(&T, &T) -> bool,
+        mut f: impl FnMut(Pos),
+    ) -> Result<usize, Error> {
+        if !grid.contains(start) || self.is_filled(start) {
+            return Ok(0);
+        }
+        self.queue.clear();
+        self.mark(start)?;
+
+        let mut count = 0;
+        while let Some(pos) = self.queue.pop_front() {
+            f(pos);
+            count += 1;
+            for (next, cell) in grid.neighbours4(pos) {
+                if !self.is_filled(next) && connected(&grid[pos], cell) {
+                    self.mark(next)?;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Fills the region around `start` like [`Regions::fill`], and measures it
+    pub fn region<T>(
+        &mut self,
+        grid: &Grid<T, W, H>,
+        start: Pos,
+        connected: impl Fn(&T, &T) -> bool,
+    ) -> Result<Region, Error> {
+        let mut region = Region {
+            start,
+            area: 0,
+            perimeter: 0,
+            corners: 0,
+            min: start,
+            max: start,
+        };
+        self.fill(grid, start, &connected, |pos| {
+            let cell = &grid[pos];
+            let around = Dir8::ALL.map(|dir| {
+                grid.get(pos.step(dir))
+                    .is_some_and(|next| connected(cell, next))
+            });
+            let inside = |dir: Dir8| around[dir.idx()];
+
+            region.area += 1;
+            region.perimeter += Dir4::ALL
+                .into_iter()
+                .filter(|&dir| !inside(dir.into()))
+                .count();
+            // A corner of the cell is a corner of the outline when the cells on both
+            // sides of it are outside, or when they're inside but the one across isn't
+            region.corners += Dir4::ALL
+                .into_iter()
+                .filter(|&dir| {
+                    let (side, across) = (Dir8::from(dir), Dir8::from(dir).turn_right());
+                    match (inside(side), inside(across.turn_right())) {
+                        (false, false) => true,
+                        (true, true) => !inside(across),
+                        _ => false,
+                    }
+                })
+                .count();
+            region.min = Pos::new(region.min.x.min(pos.x), region.min.y.min(pos.y));
+            region.max = Pos::new(region.max.x.max(pos.x), region.max.y.max(pos.y));
+        })?;
+        Ok(region)
+    }
+
+    /// Fills and measures every region that wasn't filled yet, row by row from their
+    /// top left cell, calling `f` with each
+    pub fn for_each<T>(
+        &mut self,
+        grid: &Grid<T, W, H>,
+        connected: impl Fn(&T, &T) -> bool,
+        mut f: impl FnMut(Region),
+    ) -> Result<(), Error> {
+        for (start, _) in grid.iter() {
+            if !self.is_filled(start) {
+                f(self.region(grid, start, &connected)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Labels the connected components that weren't filled yet, calling `f` with every
+    /// cell and the number of its component, counting from 0 row by row. Returns how
+    /// many components there are.
+    pub fn components<T>(
+        &mut self,
+        grid: &Grid<T, W, H>,
+        mut connected: impl FnMut(&T, &T) -> bool,
+        mut f: impl FnMut(Pos, usize),
+    ) -> Result<usize, Error> {
+        let mut count = 0;
+        for (start, _) in grid.iter() {
+            if self.fill(grid, start, &mut connected, |pos| f(pos, count))? > 0 {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    fn mark(&mut self, pos: Pos) -> Result<(), Error> {
+        self.filled[pos] = true;
+        self.queue.push_back(pos).map_err(|_| Error::QueueFull)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    /// An `A` region with a hole holding a `B`, and a `C` region in two pieces
+    const PLOTS: &[&[u8]] = &[b"AAAC", b"ABAA", b"AAAC"];
+
+    fn plots() -> Grid<u8, 4, 3> {
+        let mut grid = Grid::default();
+        for row in PLOTS {
+            grid.push_row(row, |b| b).unwrap();
+        }
+        grid
+    }
+
+    #[test]
+    fn test_region() {
+        let grid = plots();
+        let mut regions: Regions<4, 3, 16> = Regions::of(&grid);
+        let a = regions
+            .region(&grid, Pos::new(0, 0), |a, b| a == b)
+            .unwrap();
+        assert_eq!(a.area, 9);
+        assert_eq!(a.perimeter, 18);
+        // 8 on the outside, 4 around the hole
+        assert_eq!(a.sides(), 12);
+        assert_eq!((a.min, a.max), (Pos::new(0, 0), Pos::new(3, 2)));
+        assert_eq!((a.width(), a.height()), (4, 3));
+
+        // Filled cells aren't filled again
+        assert!(regions.is_filled(Pos::new(3, 1)));
+        let again = regions
+            .region(&grid, Pos::new(1, 0), |a, b| a == b)
+            .unwrap();
+        assert_eq!(again.area, 0);
+        regions.clear();
+        assert!(!regions.is_filled(Pos::new(3, 1)));
+    }
+
+    #[test]
+    fn test_for_each() {
+        let grid = plots();
+        let mut regions: Regions<4, 3, 16> = Regions::of(&grid);
+        let mut found: Vec<(u8, usize, usize), 4> = Vec::new();
+        regions
+            .for_each(
+                &grid,
+                |a, b| a == b,
+                |r| found.push((grid[r.start], r.area, r.sides())).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            &found[..],
+            &[(b'A', 9, 12), (b'C', 1, 4), (b'B', 1, 4), (b'C', 1, 4)]
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let grid = plots();
+        let mut regions: Regions<4, 3, 16> = Regions::of(&grid);
+        let mut labels = Grid::<usize, 4, 3>::filled(4, 3, 0);
+        let count = regions
+            .components(&grid, |a, b| a == b, |pos, label| labels[pos] = label)
+            .unwrap();
+        assert_eq!(count, 4);
+        assert_eq!(labels.row(0), Some(&[0, 0, 0, 1][..]));
+        assert_eq!(labels.row(1), Some(&[0, 2, 0, 0][..]));
+        assert_eq!(labels.row(2), Some(&[0, 0, 0, 3][..]));
+    }
+
+    #[test]
+    fn test_directed_fill() {
+        // Only stepping up by one, like a hiking trail
+        let mut grid: Grid<u8, 4, 2> = Grid::default();
+        grid.push_row(b"0123", |b| b - b'0').unwrap();
+        grid.push_row(b"1098", |b| b - b'0').unwrap();
+        let mut regions: Regions<4, 2, 8> = Regions::of(&grid);
+        let mut reached: Vec<Pos, 8> = Vec::new();
+        let count = regions
+            .fill(
+                &grid,
+                Pos::new(0, 0),
+                |a, b| *b == a + 1,
+                |pos| reached.push(pos).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(count, 5);
+        assert!(!reached.contains(&Pos::new(1, 1)));
+    }
+
+    #[test]
+    fn test_queue_full() {
+        let grid = plots();
+        let mut regions: Regions<4, 3, 2> = Regions::of(&grid);
+        let found = regions.region(&grid, Pos::new(0, 0), |a, b| a == b);
+        assert_eq!(found, Err(Error::QueueFull));
+    }
+
+    #[test]
+    fn test_queue_holds_the_edge() {
+        // A solid square fills with a queue as long as its diagonal
+        let mut grid: Grid<u8, 32, 32> = Grid::default();
+        for _ in 0..32 {
+            grid.push_row(&[b'A'; 32], |b| b).unwrap();
+        }
+        let mut regions: Regions<32, 32, 33> = Regions::of(&grid);
+        let region = regions
+            .region(&grid, Pos::new(0, 0), |a, b| a == b)
+            .unwrap();
+        assert_eq!(region.area, 32 * 32);
+    }
+}
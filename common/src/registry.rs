@@ -0,0 +1,109 @@
+use crate::params::Param;
+use crate::solution::Solution;
+
+/// Constructs a solution and hands it to the given closure
+type Constructor = fn(&mut dyn FnMut(&mut dyn Solution));
+
+/// A day in a registry of solutions, able to construct a fresh solver for that day
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    with: Constructor,
+    params: fn() -> &'static [Param],
+    chunked: fn() -> bool,
+}
+
+impl Day {
+    pub const fn new<S: Solution + Default>(number: u8, title: &'static str) -> Self {
+        Self {
+            number,
+            title,
+            with: with_default::<S>,
+            params: S::params,
+            chunked: S::chunked,
+        }
+    }
+
+    /// The tunables this day declares
+    pub fn params(&self) -> &'static [Param] {
+        (self.params)()
+    }
+
+    /// Whether the day takes lines longer than the line buffer in pieces
+    pub fn chunked(&self) -> bool {
+        (self.chunked)()
+    }
+
+    /// Constructs a fresh solution for this day and hands it to `f`.
+    /// The solution only lives for the duration of the call, so days that are not
+    /// being run don't take up any memory.
+    pub fn with_solution(&self, f: &mut dyn FnMut(&mut dyn Solution)) {
+        (self.with)(f)
+    }
+}
+
+fn with_default<S: Solution + Default>(f: &mut dyn FnMut(&mut dyn Solution)) {
+    let mut solution = S::default();
+    f(&mut solution);
+}
+
+/// Finds a day in the registry by its number
+pub fn find(days: &[Day], number: u8) -> Option<&Day> {
+    days.iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+    use core::fmt::Write;
+
+    #[derive(Default)]
+    struct Counter(u64);
+
+    impl Solution for Counter {
+        fn parse_line(&mut self, _line: &[u8]) {
+            self.0 += 1;
+        }
+
+        fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+            self.0.into()
+        }
+
+        fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    const DAYS: &[Day] = &[
+        Day::new::<Counter>(1, "Counting"),
+        Day::new::<Counter>(3, "More Counting"),
+    ];
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(DAYS, 3).map(|d| d.title), Some("More Counting"));
+        assert!(find(DAYS, 2).is_none());
+    }
+
+    #[test]
+    fn test_fresh_solution_per_run() {
+        let day = find(DAYS, 1).unwrap();
+        for _ in 0..2 {
+            let mut answer = Answer::Unsolved;
+            day.with_solution(&mut |s| {
+                s.parse_line(b"x");
+                answer = s.part1(&mut NoOut);
+            });
+            assert_eq!(answer, Answer::Number(1));
+        }
+    }
+
+    struct NoOut;
+
+    impl Write for NoOut {
+        fn write_str(&mut self, _s: &str) -> core::fmt::Result {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,681 @@
+use core::fmt::{self, Debug, Display, Write};
+use embedded_io::Read;
+
+use crate::io::LineReader;
+use crate::memory::{Stack, Usage};
+use crate::output::{Record, Timing, UsageRecord};
+use crate::params::{self, Params, Values};
+use crate::registry::{self, Day};
+use crate::solution::Answer;
+use crate::timing::{self, Clock};
+use heapless::Vec;
+
+/// Long enough for the longest line of any day's input.
+/// Day 9's single line is longer, but it takes it in chunks, see
+/// [`Solution::chunked`](crate::solution::Solution::chunked).
+pub const LINE_LENGTH: usize = 4096;
+
+/// Marks the end of the device's response to an input
+pub const EOT: &str = "<EOT>";
+/// Precedes an error message, when an input couldn't be handled
+pub const ERR: &str = "<ERR>";
+
+/// The header line that precedes a puzzle input, e.g. `DAY 13 PART 2`.
+/// When no part is given, both parts are reported. The day's tunables can follow as
+/// `name=value` words, e.g. `DAY 14 PART 1 width=11 height=7`, see [`params`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub params: Values,
+}
+
+impl Header {
+    pub fn parse(line: &[u8]) -> Option<Self> {
+        let line = core::str::from_utf8(line).ok()?;
+        let mut words = line.split_ascii_whitespace();
+
+        if !words.next()?.eq_ignore_ascii_case("DAY") {
+            return None;
+        }
+        let day = words.next()?.parse().ok()?;
+
+        let mut words = words.peekable();
+        let part = match words.next_if(|word| word.eq_ignore_ascii_case("PART")) {
+            None => None,
+            Some(_) => match words.next()?.parse() {
+                Ok(part @ (1 | 2)) => Some(part),
+                _ => return None,
+            },
+        };
+
+        let mut params = Values::new();
+        for word in words {
+            params.set_word(word).ok()?;
+        }
+
+        Some(Self { day, part, params })
+    }
+
+    /// Whether the given part should be reported
+    pub fn wants(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+impl Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DAY {}", self.day)?;
+        if let Some(part) = self.part {
+            write!(f, " PART {}", part)?;
+        }
+        if !self.params.is_empty() {
+            write!(f, " {}", self.params)?;
+        }
+        Ok(())
+    }
+}
+
+/// The most containers a day can report through [`Solution::usage`](crate::solution::Solution::usage)
+const MAX_USAGES: usize = 16;
+
+/// What a run is measured with, leave out what the platform doesn't have
+#[derive(Default, Clone, Copy)]
+pub struct Meters<'a> {
+    pub clock: Option<&'a dyn Clock>,
+    pub stack: Option<&'a Stack>,
+}
+
+impl Meters<'_> {
+    /// Runs a step of a run, measuring what it took
+    fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, Cost) {
+        if let Some(stack) = self.stack {
+            stack.paint();
+        }
+        let (res, cycles) = timing::measure(self.clock, f);
+        let peak_mem = self.stack.map(Stack::peak);
+        (res, Cost { cycles, peak_mem })
+    }
+}
+
+/// What a step of a run took
+#[derive(Debug, Default, Clone, Copy)]
+struct Cost {
+    cycles: Option<u64>,
+    peak_mem: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum Error<E> {
+    Read(E),
+    InvalidHeader,
+    UnknownDay(u8),
+    Params(params::Error),
+    /// A line didn't fit in the line buffer of this many bytes, and the day doesn't take
+    /// lines in chunks
+    LineTooLong(usize),
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(value: E) -> Self {
+        Error::Read(value)
+    }
+}
+
+impl<E: Debug> Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Read(e) => write!(f, "Error reading! {e:?}"),
+            Error::InvalidHeader => {
+                f.write_str("Invalid header, expected e.g. DAY 13 PART 2 or DAY 14 width=11")
+            }
+            Error::UnknownDay(day) => write!(f, "Day {day} is not available"),
+            Error::Params(e) => write!(f, "{e}"),
+            Error::LineTooLong(length) => write!(f, "A line is longer than {length} bytes"),
+        }
+    }
+}
+
+/// Reads a header line followed by a puzzle input from `reader`, routes the input to
+/// the requested day and writes the answers to `out`, as a [`Record`] per part.
+///
+/// With a clock in the `meters`, receiving and parsing the input and each part are
+/// timed, and the answers are preceded by a [`Timing`] record. With a stack, each part
+/// reports its peak memory, and the answers are followed by a [`UsageRecord`] for the
+/// stack and every container the day reports through [`Solution::usage`](crate::solution::Solution::usage).
+/// Blank lines before the header are skipped. If the header is invalid, names an
+/// unknown day or gives it values it doesn't take, the rest of the input is discarded
+/// so the next run starts cleanly. So is the rest of an input with a line longer than
+/// the line buffer, unless the day takes lines in chunks, and no part is computed.
+/// Returns Ok(false) if the transmission ended before any header was received.
+pub fn run<R, const N: usize>(
+    days: &[Day],
+    reader: &mut R,
+    lines: &mut LineReader<N>,
+    out: &mut dyn Write,
+    meters: Meters,
+) -> Result<bool, Error<R::Error>>
+where
+    R: Read,
+{
+    let header = loop {
+        lines.clear();
+        if !lines.read_until_newline(reader)? {
+            return Ok(false);
+        }
+        if !lines.line().iter().all(u8::is_ascii_whitespace) {
+            break Header::parse(lines.line());
+        }
+    };
+
+    let Some(header) = header else {
+        receive(reader, lines, |_, _| ())?;
+        return Err(Error::InvalidHeader);
+    };
+    let Some(day) = registry::find(days, header.day) else {
+        receive(reader, lines, |_, _| ())?;
+        return Err(Error::UnknownDay(header.day));
+    };
+    let params = match Params::new(day.params(), &header.params, header.part) {
+        Ok(params) => params,
+        Err(e) => {
+            receive(reader, lines, |_, _| ())?;
+            return Err(Error::Params(e));
+        }
+    };
+
+    let unsolved = (Answer::Unsolved, Cost::default());
+    let mut result = Ok((Cost::default(), unsolved.clone(), unsolved));
+    let mut usages: Vec<Usage, MAX_USAGES> = Vec::new();
+    day.with_solution(&mut |s| {
+        s.configure(&params);
+        let mut too_long = false;
+        let (received, parse) = meters.measure(|| {
+            receive(reader, lines, |line, partial| {
+                too_long |= partial && !day.chunked();
+                if !too_long {
+                    s.parse_line(line);
+                }
+            })
+        });
+        if too_long {
+            result = Err(Error::LineTooLong(N));
+            return;
+        }
+        result = received.map_err(Error::Read).map(|_| {
+            // Part 2 may build on part 1, but not the other way around
+            let p1 = meters.measure(|| s.part1(out));
+            let p2 = match header.wants(2) {
+                true => meters.measure(|| s.part2(out)),
+                false => (Answer::Unsolved, Cost::default()),
+            };
+            (parse, p1, p2)
+        });
+        s.usage(&mut |usage| {
+            let _ = usages.push(usage);
+        });
+    });
+    let (parse, (p1, p1_cost), (p2, p2_cost)) = result?;
+
+    let _ = writeln!(out, "Day {}: {}", day.number, day.title);
+    if let (Some(clock), Some(parse_cycles)) = (meters.clock, parse.cycles) {
+        let timing = Timing {
+            day: day.number,
+            hz: clock.frequency(),
+            parse_cycles,
+        };
+        let _ = writeln!(out, "{timing}");
+    }
+    for (part, answer, cost) in [(1, &p1, p1_cost), (2, &p2, p2_cost)] {
+        if header.wants(part) {
+            let record = Record {
+                cycles: cost.cycles,
+                peak_mem: cost.peak_mem,
+                ..Record::new(day.number, part, answer)
+            };
+            let _ = writeln!(out, "{record}");
+        }
+    }
+
+    if let Some(stack) = meters.stack {
+        let peak = [parse, p1_cost, p2_cost]
+            .iter()
+            .filter_map(|c| c.peak_mem)
+            .max();
+        let _ = usages.push(Usage {
+            name: "stack",
+            peak: peak.unwrap_or_default(),
+            capacity: stack.size(),
+        });
+    }
+    for usage in usages {
+        let record = UsageRecord {
+            day: day.number,
+            usage,
+        };
+        let _ = writeln!(out, "{record}");
+    }
+
+    Ok(true)
+}
+
+/// Handles one input like [`run`], then ends the response with [`EOT`].
+/// Errors are reported to `out`, prefixed with [`ERR`].
+/// Returns false if the transmission ended before any header was received.
+pub fn serve<R, const N: usize>(
+    days: &[Day],
+    reader: &mut R,
+    lines: &mut LineReader<N>,
+    out: &mut dyn Write,
+    meters: Meters,
+) -> bool
+where
+    R: Read,
+{
+    match run(days, reader, lines, out, meters) {
+        Ok(false) => return false,
+        Ok(true) => {}
+        Err(e) => {
+            let _ = writeln!(out, "{ERR} {e}");
+        }
+    }
+    let _ = writeln!(out, "{EOT}");
+    true
+}
+
+/// Hands every line to `f` until the end of the input, with whether it was cut off
+/// because it didn't fit in the buffer
+fn receive<R, const N: usize>(
+    reader: &mut R,
+    lines: &mut LineReader<N>,
+    mut f: impl FnMut(&[u8], bool),
+) -> Result<(), R::Error>
+where
+    R: Read,
+{
+    loop {
+        lines.clear();
+        if !lines.read_until_newline(reader)? {
+            return Ok(());
+        }
+        f(lines.line(), lines.is_partial());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::EotReader;
+    use crate::params::Param;
+    use crate::solution::Solution;
+    use heapless::String;
+
+    #[derive(Default)]
+    struct Counter {
+        lines: u64,
+        factor: u64,
+    }
+
+    impl Solution for Counter {
+        fn params() -> &'static [Param] {
+            &[Param {
+                name: "factor",
+                default: 2,
+                min: 1,
+                max: 10,
+                help: "What part 2 multiplies the count by",
+            }]
+        }
+
+        fn configure(&mut self, params: &Params) {
+            self.factor = params.get("factor");
+        }
+
+        fn parse_line(&mut self, _line: &[u8]) {
+            self.lines += 1;
+        }
+
+        fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+            self.lines.into()
+        }
+
+        fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+            (self.lines * self.factor).into()
+        }
+    }
+
+    /// Part 2 can't handle every input that part 1 can
+    #[derive(Default)]
+    struct Fragile;
+
+    impl Solution for Fragile {
+        fn parse_line(&mut self, _line: &[u8]) {}
+
+        fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+            1u64.into()
+        }
+
+        fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+            panic!("Part 2 needs a different input")
+        }
+
+        fn usage(&self, report: &mut dyn FnMut(Usage)) {
+            report(Usage {
+                name: "queue",
+                peak: 3,
+                capacity: 8,
+            });
+        }
+    }
+
+    /// Takes its input as one long line, in pieces
+    #[derive(Default)]
+    struct Bytes(u64);
+
+    impl Solution for Bytes {
+        fn chunked() -> bool {
+            true
+        }
+
+        fn parse_line(&mut self, line: &[u8]) {
+            self.0 += line.len() as u64;
+        }
+
+        fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+            self.0.into()
+        }
+
+        fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    const DAYS: &[Day] = &[
+        Day::new::<Counter>(13, "Counting"),
+        Day::new::<Fragile>(14, "Fragile"),
+        Day::new::<Bytes>(15, "Bytes"),
+    ];
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            Header::parse(b"DAY 13 PART 2"),
+            Some(Header {
+                day: 13,
+                part: Some(2),
+                params: Values::new(),
+            })
+        );
+        assert_eq!(
+            Header::parse(b"day 7"),
+            Some(Header {
+                day: 7,
+                part: None,
+                params: Values::new(),
+            })
+        );
+        assert_eq!(Header::parse(b"DAY 13 PART 3"), None);
+        assert_eq!(Header::parse(b"DAY x"), None);
+        assert_eq!(Header::parse(b"DAY 1 PART 1 extra"), None);
+        assert_eq!(Header::parse(b"DAY 1 PART width=3"), None);
+        assert_eq!(Header::parse(b"3   4"), None);
+    }
+
+    #[test]
+    fn test_parse_header_params() {
+        let header = Header::parse(b"DAY 14 width=11 height=7").unwrap();
+        assert_eq!((header.day, header.part), (14, None));
+        assert_eq!(header.params.get("width"), Some(11));
+        assert_eq!(header.params.get("height"), Some(7));
+
+        let header = Header::parse(b"DAY 14 PART 1 seconds=5").unwrap();
+        assert_eq!(header.part, Some(1));
+        assert_eq!(header.params.get("seconds"), Some(5));
+    }
+
+    #[test]
+    fn test_header_display() {
+        let mut params = Values::new();
+        params.set("width", 11).unwrap();
+        for header in [
+            Header {
+                day: 7,
+                part: None,
+                params: Values::new(),
+            },
+            Header {
+                day: 13,
+                part: Some(2),
+                params: Values::new(),
+            },
+            Header {
+                day: 14,
+                part: Some(1),
+                params,
+            },
+        ] {
+            let mut s: String<32> = String::new();
+            write!(s, "{}", header).unwrap();
+            assert_eq!(Header::parse(s.as_bytes()), Some(header));
+        }
+    }
+
+    #[test]
+    fn test_run_both_parts() {
+        let mut reader = EotReader::new(&b"\nDAY 13\na\nb\n\x04"[..]);
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert_eq!(
+            out,
+            concat!(
+                "Day 13: Counting\n",
+                r#"{"day":13,"part":1,"value":2,"cycles":null,"peak_mem":null}"#,
+                "\n",
+                r#"{"day":13,"part":2,"value":4,"cycles":null,"peak_mem":null}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_single_part() {
+        let mut reader = EotReader::new(&b"DAY 13 PART 2\na\n\x04"[..]);
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert_eq!(
+            out,
+            concat!(
+                "Day 13: Counting\n",
+                r#"{"day":13,"part":2,"value":2,"cycles":null,"peak_mem":null}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_chunked() {
+        let mut reader = EotReader::new(
+            &b"DAY 15 PART 1
+ThisIsAVeryLongLine
+"[..],
+        );
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert!(out.contains(r#""part":1,"value":19,"#));
+    }
+
+    #[test]
+    fn test_run_part_1_skips_part_2() {
+        let mut reader = EotReader::new(&b"DAY 14 PART 1\na\n\x04"[..]);
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert_eq!(
+            out,
+            concat!(
+                "Day 14: Fragile\n",
+                r#"{"day":14,"part":1,"value":1,"cycles":null,"peak_mem":null}"#,
+                "\n",
+                r#"{"day":14,"usage":"queue","peak":3,"capacity":8}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_timed() {
+        /// Advances one tick every time it's read
+        struct Ticks(core::cell::Cell<u64>);
+
+        impl Clock for Ticks {
+            fn now(&self) -> u64 {
+                self.0.set(self.0.get() + 1);
+                self.0.get()
+            }
+
+            fn frequency(&self) -> u64 {
+                1000
+            }
+        }
+
+        let mut reader = EotReader::new(&b"DAY 13 PART 2\na\n\x04"[..]);
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+        let clock = Ticks(Default::default());
+
+        assert!(run(
+            DAYS,
+            &mut reader,
+            &mut lines,
+            &mut out,
+            Meters {
+                clock: Some(&clock),
+                stack: None,
+            }
+        )
+        .unwrap());
+        assert_eq!(
+            out,
+            concat!(
+                "Day 13: Counting\n",
+                r#"{"day":13,"hz":1000,"parse_cycles":1}"#,
+                "\n",
+                r#"{"day":13,"part":2,"value":2,"cycles":1,"peak_mem":null}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_unknown_day_skips_input() {
+        let mut reader = EotReader::new(&b"DAY 12\na\n\x04DAY 13\na\n\x04"[..]);
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        let result = run(DAYS, &mut reader, &mut lines, &mut out, Meters::default());
+        assert!(matches!(result, Err(Error::UnknownDay(12))));
+
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert_eq!(
+            out,
+            concat!(
+                "Day 13: Counting\n",
+                r#"{"day":13,"part":1,"value":1,"cycles":null,"peak_mem":null}"#,
+                "\n",
+                r#"{"day":13,"part":2,"value":2,"cycles":null,"peak_mem":null}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_with_params() {
+        let mut reader = EotReader::new(&b"DAY 13 PART 2 factor=5\na\n\x04"[..]);
+        let mut lines = LineReader::<32>::new();
+        let mut out: String<256> = String::new();
+
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert_eq!(
+            out,
+            concat!(
+                "Day 13: Counting\n",
+                r#"{"day":13,"part":2,"value":5,"cycles":null,"peak_mem":null}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_rejects_params_and_skips_input() {
+        let mut reader =
+            EotReader::new(&b"DAY 13 factor=11\na\n\x04DAY 14 factor=2\na\n\x04DAY 13\n\x04"[..]);
+        let mut lines = LineReader::<32>::new();
+        let mut out: String<256> = String::new();
+
+        let result = run(DAYS, &mut reader, &mut lines, &mut out, Meters::default());
+        assert!(matches!(
+            result,
+            Err(Error::Params(params::Error::OutOfRange(_)))
+        ));
+        let result = run(DAYS, &mut reader, &mut lines, &mut out, Meters::default());
+        assert!(matches!(
+            result,
+            Err(Error::Params(params::Error::Unknown(_)))
+        ));
+        assert!(run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+        assert!(out.starts_with("Day 13: Counting\n"));
+    }
+
+    #[test]
+    fn test_serve() {
+        let mut reader = EotReader::new(
+            &b"DAY 13 PART 1\nabcdefghijklmnopqrstuvwxyz\nb\n\x04DAY 5\na\n\x04"[..],
+        );
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        // Day 13 doesn't take the long line in pieces
+        assert!(serve(
+            DAYS,
+            &mut reader,
+            &mut lines,
+            &mut out,
+            Meters::default()
+        ));
+        assert_eq!(out, "<ERR> A line is longer than 16 bytes\n<EOT>\n");
+
+        out.clear();
+        assert!(serve(
+            DAYS,
+            &mut reader,
+            &mut lines,
+            &mut out,
+            Meters::default()
+        ));
+        assert_eq!(out, "<ERR> Day 5 is not available\n<EOT>\n");
+
+        assert!(!serve(
+            DAYS,
+            &mut reader,
+            &mut lines,
+            &mut out,
+            Meters::default()
+        ));
+    }
+
+    #[test]
+    fn test_run_nothing_received() {
+        let mut reader = EotReader::new(&b"\x04"[..]);
+        let mut lines = LineReader::<16>::new();
+        let mut out: String<256> = String::new();
+
+        assert!(!run(DAYS, &mut reader, &mut lines, &mut out, Meters::default()).unwrap());
+    }
+}
@@ -0,0 +1,167 @@
+use core::fmt::{self, Display, Write};
+use embedded_io::Read;
+use heapless::String;
+
+use crate::io::LineReader;
+use crate::memory::Usage;
+use crate::params::{Param, Params};
+
+/// The answer to one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// No answer was computed for this part
+    Unsolved,
+    Number(u64),
+    Text(String<64>),
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as u64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Number(value as u64)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsolved => f.write_str("unsolved"),
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+/// A single day's puzzle solver.
+///
+/// The input is fed in line by line through `parse_line`, after which both parts are
+/// computed. Part 2 is always computed after part 1, so it may reuse part 1's results.
+/// Part 2 is skipped when only part 1 is asked for.
+/// Any diagnostics a part wants to show can be written to `out`.
+/// A day with tunables declares them in `params`, and is given their values for the
+/// run through `configure` before any input.
+pub trait Solution {
+    /// The tunables this day declares, see [`params`](crate::params)
+    fn params() -> &'static [Param]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// Whether a line longer than the line buffer is handed to `parse_line` in pieces,
+    /// for days whose input is one long line. Otherwise such a line is an error.
+    fn chunked() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Takes the values of the tunables for this run, before any input is parsed
+    fn configure(&mut self, _params: &Params) {}
+
+    /// Consumes one line of puzzle input, excluding the newline
+    fn parse_line(&mut self, line: &[u8]);
+
+    /// Computes the answer to part 1
+    fn part1(&mut self, out: &mut dyn Write) -> Answer;
+
+    /// Computes the answer to part 2
+    fn part2(&mut self, out: &mut dyn Write) -> Answer;
+
+    /// Reports how full the fixed-capacity containers got, once both parts are done
+    fn usage(&self, _report: &mut dyn FnMut(Usage)) {}
+}
+
+/// Feeds every line from `reader` into the solution, until the end of transmission
+pub fn parse<R, const N: usize>(
+    solution: &mut dyn Solution,
+    reader: &mut R,
+    lines: &mut LineReader<N>,
+) -> Result<(), R::Error>
+where
+    R: Read,
+{
+    loop {
+        lines.clear();
+        if !lines.read_until_newline(reader)? {
+            return Ok(());
+        }
+        solution.parse_line(lines.line());
+    }
+}
+
+/// Parses all input from `reader` and computes both parts
+pub fn solve<R, const N: usize>(
+    solution: &mut dyn Solution,
+    reader: &mut R,
+    lines: &mut LineReader<N>,
+    out: &mut dyn Write,
+) -> Result<(Answer, Answer), R::Error>
+where
+    R: Read,
+{
+    parse(solution, reader, lines)?;
+    let p1 = solution.part1(out);
+    let p2 = solution.part2(out);
+    Ok((p1, p2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    #[derive(Default)]
+    struct Summer {
+        lines: Vec<u64, 8>,
+    }
+
+    impl Solution for Summer {
+        fn parse_line(&mut self, line: &[u8]) {
+            let num = core::str::from_utf8(line).unwrap().parse().unwrap();
+            self.lines.push(num).unwrap();
+        }
+
+        fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+            self.lines.iter().sum::<u64>().into()
+        }
+
+        fn part2(&mut self, out: &mut dyn Write) -> Answer {
+            write!(out, "max").unwrap();
+            self.lines.iter().max().copied().unwrap().into()
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut reader: &[u8] = b"1\n5\n3\n";
+        let mut lines = LineReader::<8>::new();
+        let mut out: String<8> = String::new();
+        let mut solution = Summer::default();
+
+        let answers = solve(&mut solution, &mut reader, &mut lines, &mut out).unwrap();
+
+        assert_eq!(answers, (Answer::Number(9), Answer::Number(5)));
+        assert_eq!(out, "max");
+    }
+
+    #[test]
+    fn test_answer_display() {
+        let mut s: String<16> = String::new();
+        write!(s, "{} {}", Answer::Number(42), Answer::Unsolved).unwrap();
+        assert_eq!(s, "42 unsolved");
+    }
+}
@@ -1,6 +1,189 @@
+//! Exact solutions of square systems of linear equations with integer coefficients.
+//!
+//! The system is brought to echelon form with fraction-free (Bareiss) elimination, so
+//! every number stays an integer and every division is exact. The work is done in
+//! `i128` with checked arithmetic, so a system too big for that fails with
+//! [`Error::Overflow`] instead of a wrong answer.
+use core::fmt::{self, Display};
+
+use crate::num;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A number outgrew an `i128`
+    Overflow,
+    /// The solutions span more than a line, which isn't searched
+    TooManyFree,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Overflow => f.write_str("The system's numbers overflow"),
+            Error::TooManyFree => f.write_str("The solutions span more than a line"),
+        }
+    }
+}
+
+/// The solutions of `N` equations in `N` unknowns. The solutions are rational, each
+/// unknown is a numerator over a common denominator `den`, which is positive.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Solutions<const N: usize> {
+    /// The equations contradict each other
+    None,
+    /// Exactly one solution, `num / den`
+    Unique { num: [i128; N], den: i128 },
+    /// A line of solutions, `(base + t * step) / den` for every rational `t`
+    Line {
+        base: [i128; N],
+        step: [i128; N],
+        den: i128,
+    },
+    /// Solutions in `free` dimensions, as many as there are unknowns that can be chosen
+    /// freely
+    Infinite { free: usize },
+}
+
+impl<const N: usize> Solutions<N> {
+    /// The solution if there's exactly one and it's all integers
+    pub fn integer(&self) -> Option<[i64; N]> {
+        match self {
+            Solutions::Unique { num, den } => integers(num, *den),
+            _ => None,
+        }
+    }
+
+    /// The integer solution with every unknown in `0..=max` for which the sum of the
+    /// unknowns weighted by `cost` is the lowest. On a line that's one of the ends of the
+    /// feasible part of it, so it's found without searching.
+    pub fn min_cost(&self, cost: [i64; N], max: i64) -> Result<Option<[i64; N]>, Error> {
+        let (base, step, den) = match self {
+            Solutions::None => return Ok(None),
+            Solutions::Unique { num, den } => {
+                let x = integers(num, *den).filter(|x| x.iter().all(|&x| (0..=max).contains(&x)));
+                return Ok(x);
+            }
+            Solutions::Line { base, step, den } => (base, step, *den),
+            Solutions::Infinite { .. } => return Err(Error::TooManyFree),
+        };
+
+        // The `t` that make every unknown an integer: step * t ≡ -base (mod den)
+        let mut t = Progression { first: 0, every: 1 };
+        for (&b, &s) in base.iter().zip(step) {
+            let Some(progression) = Progression::solving(s, neg(b)?, den)? else {
+                return Ok(None);
+            };
+            let Some(both) = t.intersect(&progression)? else {
+                return Ok(None);
+            };
+            t = both;
+        }
+
+        // The `t` that keep every unknown in 0..=max: 0 <= base + t * step <= max * den
+        let top = mul(max.into(), den)?;
+        let (mut lo, mut hi) = (None::<i128>, None::<i128>);
+        for (&b, &s) in base.iter().zip(step) {
+            let (from, to) = match s.signum() {
+                0 if b >= 0 && b <= top => continue,
+                0 => return Ok(None),
+                1 => (div_ceil(neg(b)?, s), div_floor(sub(top, b)?, s)),
+                _ => (div_ceil(sub(top, b)?, s), div_floor(neg(b)?, s)),
+            };
+            lo = Some(lo.map_or(from, |lo| lo.max(from)));
+            hi = Some(hi.map_or(to, |hi| hi.min(to)));
+        }
+        let (Some(lo), Some(hi)) = (lo, hi) else {
+            // Only when every step is 0, which a line never has
+            return Ok(None);
+        };
+
+        let slope = cost
+            .iter()
+            .zip(step)
+            .try_fold(0i128, |sum, (&c, &s)| add(sum, mul(c.into(), s)?))?;
+        let t = match slope >= 0 {
+            true => t.at_least(lo)?,
+            false => t.at_most(hi)?,
+        };
+        if t < lo || t > hi {
+            return Ok(None);
+        }
+
+        let mut num = [0; N];
+        for (n, (&b, &s)) in num.iter_mut().zip(base.iter().zip(step)) {
+            *n = add(b, mul(t, s)?)?;
+        }
+        Ok(integers(&num, den))
+    }
+}
+
+/// Solves `a x = b` for `x`
+pub fn solve<const N: usize>(a: [[i64; N]; N], b: [i64; N]) -> Result<Solutions<N>, Error> {
+    let mut m = a.map(|row| row.map(i128::from));
+    let mut rhs = b.map(i128::from);
+
+    // Bareiss elimination: every entry below a pivot row becomes a minor of the
+    // original matrix, so dividing by the previous pivot is exact
+    let mut pivots = [0; N];
+    let mut rank = 0;
+    let mut prev = 1;
+    for col in 0..N {
+        let Some(row) = (rank..N).find(|&r| m[r][col] != 0) else {
+            continue;
+        };
+        m.swap(rank, row);
+        rhs.swap(rank, row);
+        let (above, below) = m.split_at_mut(rank + 1);
+        let pivot_row = &above[rank];
+        let pivot = pivot_row[col];
+        for (r, row) in below
+            .iter_mut()
+            .enumerate()
+            .map(|(i, row)| (rank + 1 + i, row))
+        {
+            let factor = row[col];
+            for (x, &p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x = sub(mul(pivot, *x)?, mul(factor, p)?)? / prev;
+            }
+            rhs[r] = sub(mul(pivot, rhs[r])?, mul(factor, rhs[rank])?)? / prev;
+        }
+        pivots[rank] = col;
+        prev = pivot;
+        rank += 1;
+    }
+
+    if rhs[rank..].iter().any(|&r| r != 0) {
+        return Ok(Solutions::None);
+    }
+    let pivots = &pivots[..rank];
+    let free = N - rank;
+    if free > 1 {
+        return Ok(Solutions::Infinite { free });
+    }
+
+    // The last pivot is the determinant of the pivot columns, which every unknown has
+    // as its denominator by Cramer's rule
+    let den = prev;
+    let base = back_substitute(&m, pivots, den, |r| rhs[r])?;
+    let solutions = match (0..N).find(|c| !pivots.contains(c)) {
+        None => Solutions::Unique { num: base, den },
+        Some(f) => {
+            // Moving the free unknown `t` to the right-hand side
+            let mut step = back_substitute(&m, pivots, den, |r| m[r][f])?;
+            for s in &mut step {
+                *s = neg(*s)?;
+            }
+            step[f] = den;
+            Solutions::Line { base, step, den }
+        }
+    };
+    Ok(normalise(solutions))
+}
+
 /// Solves a system of two linear equations in the form:
 /// a1x + b1y = k1
 /// a2x + b2y = k2
+/// for its only solution in integers, if it has one.
 pub fn solve_2x2_system(
     a1: i64,
     b1: i64,
@@ -9,29 +192,130 @@ pub fn solve_2x2_system(
     b2: i64,
     k2: i64,
 ) -> Option<(i64, i64)> {
-    // Check if a1 is zero (would cause division by zero in substitution)
-    if a1 == 0 {
-        return None;
+    let [x, y] = solve([[a1, b1], [a2, b2]], [k1, k2]).ok()?.integer()?;
+    Some((x, y))
+}
+
+/// The numerators of the pivot unknowns over `den`, for the right-hand side `rhs(row)`
+fn back_substitute<const N: usize>(
+    m: &[[i128; N]; N],
+    pivots: &[usize],
+    den: i128,
+    rhs: impl Fn(usize) -> i128,
+) -> Result<[i128; N], Error> {
+    let mut num = [0; N];
+    for (r, &col) in pivots.iter().enumerate().rev() {
+        let mut sum = mul(den, rhs(r))?;
+        for &c in &pivots[r + 1..] {
+            sum = sub(sum, mul(m[r][c], num[c])?)?;
+        }
+        num[col] = sum / m[r][col];
+    }
+    Ok(num)
+}
+
+/// Makes the denominator positive
+fn normalise<const N: usize>(solutions: Solutions<N>) -> Solutions<N> {
+    match solutions {
+        Solutions::Unique { num, den } if den < 0 => Solutions::Unique {
+            num: num.map(|n| -n),
+            den: -den,
+        },
+        Solutions::Line { base, step, den } if den < 0 => Solutions::Line {
+            base: base.map(|n| -n),
+            step: step.map(|n| -n),
+            den: -den,
+        },
+        solutions => solutions,
+    }
+}
+
+fn integers<const N: usize>(num: &[i128; N], den: i128) -> Option<[i64; N]> {
+    let mut x = [0; N];
+    for (x, &n) in x.iter_mut().zip(num) {
+        if n % den != 0 {
+            return None;
+        }
+        *x = (n / den).try_into().ok()?;
     }
+    Some(x)
+}
 
-    let numerator_y = k2 * a1 - a2 * k1;
-    let denominator = b2 * a1 - a2 * b1;
+/// The integers `first + k * every` for every integer `k`
+#[derive(Debug, Clone, Copy)]
+struct Progression {
+    first: i128,
+    every: i128,
+}
 
-    // Check if denominator is zero (parallel or coincident lines)
-    // Or divisions would not be a whole number (we want integer solutions only)
-    if denominator == 0 || numerator_y % denominator != 0 {
-        return None;
+impl Progression {
+    /// The `t` with `a * t ≡ b (mod m)`, None if there aren't any
+    fn solving(a: i128, b: i128, m: i128) -> Result<Option<Self>, Error> {
+        let g = num::gcd(a, m).ok_or(Error::Overflow)?;
+        if b % g != 0 {
+            return Ok(None);
+        }
+        let every = m / g;
+        let first = num::mod_inverse(a / g, every)
+            .and_then(|inverse| num::mul_mod(b / g, inverse, every))
+            .ok_or(Error::Overflow)?;
+        Ok(Some(Self { first, every }))
     }
 
-    let y = numerator_y / denominator;
+    /// The integers in both progressions, by the Chinese remainder theorem
+    fn intersect(&self, other: &Self) -> Result<Option<Self>, Error> {
+        let g = num::gcd(self.every, other.every).ok_or(Error::Overflow)?;
+        if sub(other.first, self.first)? % g != 0 {
+            return Ok(None);
+        }
+        let (first, every) = num::crt(&[(self.first, self.every), (other.first, other.every)])
+            .ok_or(Error::Overflow)?;
+        Ok(Some(Self { first, every }))
+    }
 
-    let numerator_x = k1 - b1 * y;
-    if numerator_x % a1 != 0 {
-        return None;
+    /// The smallest member that's at least `lo`
+    fn at_least(&self, lo: i128) -> Result<i128, Error> {
+        add(lo, sub(self.first, lo)?.rem_euclid(self.every))
     }
-    let x = numerator_x / a1;
 
-    Some((x, y))
+    /// The largest member that's at most `hi`
+    fn at_most(&self, hi: i128) -> Result<i128, Error> {
+        sub(hi, sub(hi, self.first)?.rem_euclid(self.every))
+    }
+}
+
+/// `a / b` rounded down, for either sign of `b`
+fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    match a % b != 0 && (a < 0) != (b < 0) {
+        true => q - 1,
+        false => q,
+    }
+}
+
+/// `a / b` rounded up, for either sign of `b`
+fn div_ceil(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    match a % b != 0 && (a < 0) == (b < 0) {
+        true => q + 1,
+        false => q,
+    }
+}
+
+fn add(a: i128, b: i128) -> Result<i128, Error> {
+    a.checked_add(b).ok_or(Error::Overflow)
+}
+
+fn sub(a: i128, b: i128) -> Result<i128, Error> {
+    a.checked_sub(b).ok_or(Error::Overflow)
+}
+
+fn mul(a: i128, b: i128) -> Result<i128, Error> {
+    a.checked_mul(b).ok_or(Error::Overflow)
+}
+
+fn neg(a: i128) -> Result<i128, Error> {
+    a.checked_neg().ok_or(Error::Overflow)
 }
 
 /// Unit tests for the solver
@@ -66,11 +350,72 @@ mod tests {
     }
 
     #[test]
-    fn test_division_by_zero() {
-        // Test system with a1 = 0:
+    fn test_zero_leading_coefficient() {
+        // Test system with a1 = 0, which only needs the rows swapped:
         // 0x + y = 1
         // x + y = 2
         let result = solve_2x2_system(0, 1, 1, 1, 1, 2);
-        assert_eq!(result, None);
+        assert_eq!(result, Some((1, 1)));
+    }
+
+    #[test]
+    fn test_rational_solution() {
+        // 2x = 1, 3y = 2
+        let solutions = solve([[2, 0], [0, 3]], [1, 2]).unwrap();
+        assert_eq!(
+            solutions,
+            Solutions::Unique {
+                num: [3, 4],
+                den: 6
+            }
+        );
+        assert_eq!(solutions.integer(), None);
+    }
+
+    #[test]
+    fn test_3x3() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let solutions = solve([[1, 1, 1], [0, 2, 5], [2, 5, -1]], [6, -4, 27]).unwrap();
+        assert_eq!(solutions.integer(), Some([5, 3, -2]));
+    }
+
+    #[test]
+    fn test_infinite() {
+        let solutions = solve([[1, 2, 3], [2, 4, 6], [3, 6, 9]], [1, 2, 3]).unwrap();
+        assert_eq!(solutions, Solutions::Infinite { free: 2 });
+        assert_eq!(solutions.min_cost([1, 1, 1], 10), Err(Error::TooManyFree));
+        let solutions = solve([[1, 2], [2, 4]], [1, 3]).unwrap();
+        assert_eq!(solutions, Solutions::None);
+    }
+
+    #[test]
+    fn test_min_cost_on_a_line() {
+        // Collinear buttons: 2a + 4b = 20, 3a + 6b = 30, where a costs 3 and b 1
+        let solutions = solve([[2, 4], [3, 6]], [20, 30]).unwrap();
+        assert!(matches!(solutions, Solutions::Line { .. }));
+        assert_eq!(solutions.min_cost([3, 1], 100), Ok(Some([0, 5])));
+        // When b costs more than twice as much, pressing a is cheaper
+        assert_eq!(solutions.min_cost([3, 7], 100), Ok(Some([10, 0])));
+        // Capped at 4 presses each, b alone can't do it
+        assert_eq!(solutions.min_cost([3, 1], 4), Ok(Some([2, 4])));
+        assert_eq!(solutions.min_cost([3, 1], 1), Ok(None));
+
+        // 4a + 6b = 9 has no integer solutions at all
+        let solutions = solve([[4, 6], [8, 12]], [9, 18]).unwrap();
+        assert_eq!(solutions.min_cost([3, 1], 100), Ok(None));
+    }
+
+    #[test]
+    fn test_min_cost_unique() {
+        let solutions = solve([[94, 22], [34, 67]], [8400, 5400]).unwrap();
+        assert_eq!(solutions.min_cost([3, 1], 100), Ok(Some([80, 40])));
+        assert_eq!(solutions.min_cost([3, 1], 50), Ok(None));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = i64::MAX;
+        let result = solve([[big, big - 1, 1], [1, big, big], [big, 1, big]], [big; 3]);
+        assert_eq!(result, Err(Error::Overflow));
     }
 }
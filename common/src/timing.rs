@@ -0,0 +1,64 @@
+//! Measures how long the steps of a run take, on whatever timer the platform has:
+//! the system timer on the device, `std::time::Instant` on the host.
+
+/// A monotonic timer
+pub trait Clock {
+    /// Ticks since an arbitrary point in time, never decreasing
+    fn now(&self) -> u64;
+
+    /// How many ticks make a second
+    fn frequency(&self) -> u64;
+}
+
+/// Runs `f`, returning its result and the ticks it took if there's a clock
+pub fn measure<T>(clock: Option<&dyn Clock>, f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let start = clock.map(|c| c.now());
+    let res = f();
+    let elapsed = clock
+        .zip(start)
+        .map(|(c, start)| c.now().saturating_sub(start));
+    (res, elapsed)
+}
+
+/// Converts ticks of a clock running at `frequency` to microseconds
+pub fn to_micros(ticks: u64, frequency: u64) -> u64 {
+    let micros = ticks as u128 * 1_000_000 / frequency.max(1) as u128;
+    micros.min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Advances 10 ticks every time it's read
+    struct Fake(Cell<u64>);
+
+    impl Clock for Fake {
+        fn now(&self) -> u64 {
+            self.0.set(self.0.get() + 10);
+            self.0.get()
+        }
+
+        fn frequency(&self) -> u64 {
+            1000
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let clock = Fake(Cell::new(0));
+        let (res, ticks) = measure(Some(&clock), || clock.now() * 2);
+        assert_eq!(res, 40);
+        assert_eq!(ticks, Some(20));
+
+        assert_eq!(measure(None, || 1), (1, None));
+    }
+
+    #[test]
+    fn test_to_micros() {
+        assert_eq!(to_micros(16_000_000, 16_000_000), 1_000_000);
+        assert_eq!(to_micros(3, 16_000_000), 0);
+        assert_eq!(to_micros(u64::MAX, 1_000_000), u64::MAX);
+    }
+}
@@ -0,0 +1,55 @@
+#![no_std]
+use aoc_common::memory::Usage;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::Vec;
+
+const SIZE: usize = 1000;
+
+#[derive(Default)]
+pub struct Day1 {
+    left: Vec<i32, SIZE>,
+    right: Vec<i32, SIZE>,
+}
+
+impl Solution for Day1 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+        let mut parts = line.split(' ');
+
+        let l = parts.next().unwrap().parse().unwrap();
+        self.left.push(l).unwrap();
+        let r = parts.next_back().unwrap().parse().unwrap();
+        self.right.push(r).unwrap();
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.left.sort_unstable();
+        self.right.sort_unstable();
+
+        let mut sum: u32 = 0;
+        for (l, r) in self.left.iter().zip(self.right.iter()) {
+            sum += l.abs_diff(*r);
+        }
+        sum.into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        let mut similarity: i32 = 0;
+        for num in &self.left {
+            let count = self.right.iter().filter(|x| *x == num).count() as i32;
+            similarity += num * count;
+        }
+        (similarity as u32).into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        for (name, list) in [("left", &self.left), ("right", &self.right)] {
+            report(Usage {
+                name,
+                peak: list.len(),
+                capacity: list.capacity(),
+            });
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#![no_std]
+use aoc_common::grid::{Grid, Pos as Coord};
+use aoc_common::memory::Usage;
+use aoc_common::region::Regions;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+
+const MAPDIM: usize = 55;
+type Map = Grid<u8, MAPDIM, MAPDIM>;
+type Trails = Regions<MAPDIM, MAPDIM, 256>;
+
+#[derive(Default)]
+pub struct Day10 {
+    map: Map,
+}
+
+impl Solution for Day10 {
+    fn parse_line(&mut self, line: &[u8]) {
+        self.map
+            .push_row(line, |b| (b as char).to_digit(10).unwrap_or(13) as u8)
+            .unwrap();
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        let mut trails = Trails::of(&self.map);
+        self.trailheads()
+            .map(|pos| score(&self.map, &mut trails, pos))
+            .sum::<usize>()
+            .into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        let ratings = ratings(&self.map);
+        self.trailheads()
+            .map(|pos| ratings[pos])
+            .sum::<usize>()
+            .into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "map",
+            peak: self.map.height(),
+            capacity: MAPDIM,
+        });
+    }
+}
+
+impl Day10 {
+    /// All zeroes on the map, where we start walking
+    fn trailheads(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.map
+            .iter()
+            .filter(|(_, height)| **height == 0)
+            .map(|(pos, _)| pos)
+    }
+}
+
+/// The number of nines that can be reached from `pos`, going up one step at a time
+fn score(map: &Map, trails: &mut Trails, pos: Coord) -> usize {
+    trails.clear();
+    let mut nines = 0;
+    trails
+        .fill(
+            map,
+            pos,
+            |height, next| *next == height + 1,
+            |pos| {
+                nines += (map[pos] == 9) as usize;
+            },
+        )
+        .unwrap();
+    nines
+}
+
+/// The number of trails from every position to any nine, counted from the nines down
+fn ratings(map: &Map) -> Grid<usize, MAPDIM, MAPDIM> {
+    let mut ratings = Grid::filled(map.width(), map.height(), 0);
+    for (pos, _) in map.iter().filter(|(_, height)| **height == 9) {
+        ratings[pos] = 1;
+    }
+    for height in (0..9).rev() {
+        for (pos, _) in map.iter().filter(|(_, h)| **h == height) {
+            ratings[pos] = map
+                .neighbours4(pos)
+                .filter(|(_, next)| **next == height + 1)
+                .map(|(next, _)| ratings[next])
+                .sum();
+        }
+    }
+    ratings
+}
@@ -0,0 +1,136 @@
+#![no_std]
+use aoc_common::memo::{Cache as Memo, Eviction};
+use aoc_common::memory::Usage;
+use aoc_common::num;
+use aoc_common::params::{Param, Params};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::Vec;
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "blinks1",
+        default: 25,
+        min: 0,
+        max: 75,
+        help: "How many times part 1 blinks",
+    },
+    Param {
+        name: "blinks2",
+        default: 75,
+        min: 0,
+        max: 75,
+        help: "How many times part 2 blinks",
+    },
+];
+
+/// The number of stones a stone turns into after a number of blinks
+type Cache = Memo<(u64, u8), u64, 8192>;
+
+pub struct Day11 {
+    stones: Vec<u64, 32>,
+    cache: Cache,
+    blinks: [u8; 2],
+}
+
+impl Default for Day11 {
+    fn default() -> Self {
+        Self {
+            stones: Vec::new(),
+            cache: Cache::new(Eviction::Lru),
+            blinks: [25, 75],
+        }
+    }
+}
+
+impl Solution for Day11 {
+    fn params() -> &'static [Param] {
+        PARAMS
+    }
+
+    fn configure(&mut self, params: &Params) {
+        self.blinks = [params.get("blinks1"), params.get("blinks2")];
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+        for s in line.split(' ') {
+            self.stones.push(s.parse().unwrap()).unwrap();
+        }
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        self.count_stones(self.blinks[0], out)
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        self.count_stones(self.blinks[1], out)
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "stones",
+            peak: self.stones.len(),
+            capacity: self.stones.capacity(),
+        });
+        report(Usage {
+            name: "cache",
+            peak: self.cache.len(),
+            capacity: self.cache.capacity(),
+        });
+    }
+}
+
+impl Day11 {
+    /// Counts the stones after a number of blinks, unless a stone or the count gets too
+    /// big for 64 bits
+    fn count_stones(&mut self, blinks: u8, out: &mut dyn Write) -> Answer {
+        let cache = &mut self.cache;
+        let mut overflow = false;
+        let count = self.stones.iter().try_fold(0u64, |acc, num| {
+            acc.checked_add(simulate_blinks(*num, blinks, cache, &mut overflow))
+        });
+        match count {
+            Some(count) if !overflow => count.into(),
+            _ => {
+                // What was cached on the way may be wrong
+                cache.clear();
+                let _ = writeln!(out, "The stones don't fit in 64 bits");
+                Answer::Unsolved
+            }
+        }
+    }
+}
+
+/// The number of stones a stone turns into. Sets `overflow` and counts 0 if a stone or
+/// the count doesn't fit in 64 bits.
+fn simulate_blinks(stone: u64, blinks: u8, cache: &mut Cache, overflow: &mut bool) -> u64 {
+    if blinks == 0 {
+        return 1;
+    }
+
+    cache.memoize((stone, blinks), |cache| {
+        let next_blinks = blinks - 1;
+        let n_digits = num::count_digits(stone);
+
+        if stone == 0 {
+            simulate_blinks(1, next_blinks, cache, overflow)
+        } else if n_digits.is_multiple_of(2) {
+            let (l, r) = num::split(stone, n_digits / 2);
+            let l = simulate_blinks(l, next_blinks, cache, overflow);
+            let r = simulate_blinks(r, next_blinks, cache, overflow);
+            l.checked_add(r).unwrap_or_else(|| {
+                *overflow = true;
+                0
+            })
+        } else {
+            match stone.checked_mul(2024) {
+                Some(stone) => simulate_blinks(stone, next_blinks, cache, overflow),
+                None => {
+                    *overflow = true;
+                    0
+                }
+            }
+        }
+    })
+}
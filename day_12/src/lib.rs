@@ -0,0 +1,62 @@
+#![no_std]
+use aoc_common::grid::Grid;
+use aoc_common::memory::Usage;
+use aoc_common::region::{self, Region, Regions};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+
+const MAP_SIZE: usize = 140;
+type Map = Grid<u8, MAP_SIZE, MAP_SIZE>;
+type Plots = Regions<MAP_SIZE, MAP_SIZE, 1024>;
+
+#[derive(Default)]
+pub struct Day12 {
+    map: Map,
+}
+
+impl Solution for Day12 {
+    fn parse_line(&mut self, line: &[u8]) {
+        self.map.push_row(line, |b| b).unwrap();
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        report(calculate_costs(&self.map, |region| region.perimeter), out)
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        report(calculate_costs(&self.map, |region| region.sides()), out)
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "map",
+            peak: self.map.height(),
+            capacity: MAP_SIZE,
+        });
+    }
+}
+
+/// Sums the area of every region times the length of its fence, as `fence` measures it
+fn calculate_costs(map: &Map, fence: impl Fn(&Region) -> usize) -> Result<u64, region::Error> {
+    let mut plots = Plots::of(map);
+    let mut sum = 0;
+    plots.for_each(
+        map,
+        |a, b| a == b,
+        |region| {
+            sum += (region.area * fence(&region)) as u64;
+        },
+    )?;
+    Ok(sum)
+}
+
+/// The answer, or why there's none, e.g. a region too big to fill
+fn report(costs: Result<u64, region::Error>, out: &mut dyn Write) -> Answer {
+    match costs {
+        Ok(sum) => sum.into(),
+        Err(e) => {
+            let _ = writeln!(out, "{e}");
+            Answer::Unsolved
+        }
+    }
+}
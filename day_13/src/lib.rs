@@ -0,0 +1,129 @@
+#![no_std]
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::solver;
+use core::fmt::Write;
+
+const P2_OFFSET: u64 = 10_000_000_000_000;
+
+#[derive(Default)]
+pub struct Day13 {
+    read_idx: u8,
+    current_machine: ClawMachine,
+    /// The tokens to win every prize that can be won, by part
+    tokens: [u64; 2],
+    /// Why a machine couldn't be solved, by part
+    errors: [Option<solver::Error>; 2],
+}
+
+impl Solution for Day13 {
+    fn parse_line(&mut self, line: &[u8]) {
+        match self.read_idx % 4 {
+            0 => {
+                let (a_x, a_y) = get_coords(line, b'+');
+                self.current_machine = ClawMachine {
+                    a_x,
+                    a_y,
+                    ..Default::default()
+                }
+            }
+            1 => {
+                let (b_x, b_y) = get_coords(line, b'+');
+                self.current_machine.b_x = b_x;
+                self.current_machine.b_y = b_y;
+            }
+            2 => {
+                let (p_x, p_y) = get_coords(line, b'=');
+                self.current_machine.p_x = p_x;
+                self.current_machine.p_y = p_y;
+                self.add_tokens(0, 100);
+
+                // A prize that far off is too far for the solver, which reports it
+                let machine = &mut self.current_machine;
+                machine.p_x = machine.p_x.saturating_add(P2_OFFSET);
+                machine.p_y = machine.p_y.saturating_add(P2_OFFSET);
+                self.add_tokens(1, i64::MAX);
+            }
+            3 => (),
+            _ => unreachable!(),
+        }
+
+        self.read_idx = self.read_idx.wrapping_add(1);
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        self.answer(0, out)
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        self.answer(1, out)
+    }
+}
+
+impl Day13 {
+    /// Adds the tokens to win the current machine's prize to a part's total, pressing
+    /// each button at most `limit` times
+    fn add_tokens(&mut self, part: usize, limit: i64) {
+        match solve(&self.current_machine, limit) {
+            Ok(tokens) => self.tokens[part] += tokens.unwrap_or(0),
+            Err(e) => {
+                self.errors[part].get_or_insert(e);
+            }
+        }
+    }
+
+    fn answer(&self, part: usize, out: &mut dyn Write) -> Answer {
+        match &self.errors[part] {
+            None => self.tokens[part].into(),
+            Some(e) => {
+                let _ = writeln!(out, "{e}");
+                Answer::Unsolved
+            }
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct ClawMachine {
+    a_x: u64,
+    a_y: u64,
+    b_x: u64,
+    b_y: u64,
+    p_x: u64,
+    p_y: u64,
+}
+
+fn get_coords(line: &[u8], prefix: u8) -> (u64, u64) {
+    let mut parts = line
+        .split(|b| b.is_ascii_whitespace() || *b == b',' || *b == prefix)
+        .filter_map(|b| core::str::from_utf8(b).ok().and_then(|s| s.parse().ok()));
+
+    (parts.next().unwrap(), parts.next().unwrap())
+}
+
+/// The fewest tokens to win the prize, pressing each button at most `limit` times.
+/// When both buttons move the claw the same way there's a line of ways to win, of
+/// which the cheapest is picked.
+/// Numbers that don't fit the solver fail with [`solver::Error::Overflow`].
+fn solve(machine: &ClawMachine, limit: i64) -> Result<Option<u64>, solver::Error> {
+    let n = |n: u64| i64::try_from(n).map_err(|_| solver::Error::Overflow);
+    let solutions = solver::solve(
+        [
+            [n(machine.a_x)?, n(machine.b_x)?],
+            [n(machine.a_y)?, n(machine.b_y)?],
+        ],
+        [n(machine.p_x)?, n(machine.p_y)?],
+    )?;
+    let presses = match solutions.min_cost([3, 1], limit) {
+        // Neither button moves the claw, and the prize is right under it
+        Err(solver::Error::TooManyFree) => return Ok(Some(0)),
+        presses => presses?,
+    };
+    let Some([na, nb]) = presses else {
+        return Ok(None);
+    };
+    na.checked_mul(3)
+        .and_then(|tokens| tokens.checked_add(nb))
+        .and_then(|tokens| u64::try_from(tokens).ok())
+        .map(Some)
+        .ok_or(solver::Error::Overflow)
+}
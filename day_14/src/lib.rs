@@ -0,0 +1,192 @@
+#![no_std]
+use aoc_common::bits::{words, BitGrid};
+use aoc_common::geom::Point;
+use aoc_common::grid::Pos;
+use aoc_common::memory::Usage;
+use aoc_common::num;
+use aoc_common::params::{Param, Params};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::Vec;
+
+type Bots = Vec<Bot, 500>;
+
+/// The robots walk off one edge of the room and come back in at the opposite one.
+/// This is the size of the puzzle's room, the examples use a smaller one.
+const SIZE: Point<i16> = Point::new(101, 103);
+/// Where the robots are at one second
+type Frame = BitGrid<{ words(SIZE.x as usize) }, { SIZE.y as usize }>;
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "width",
+        default: SIZE.x as i64,
+        min: 1,
+        max: SIZE.x as i64,
+        help: "The width of the room",
+    },
+    Param {
+        name: "height",
+        default: SIZE.y as i64,
+        min: 1,
+        max: SIZE.y as i64,
+        help: "The height of the room",
+    },
+    Param {
+        name: "seconds",
+        default: 100,
+        min: 0,
+        max: 1_000_000,
+        help: "How long the robots walk before part 1 checks the safety factor",
+    },
+    Param {
+        name: "image",
+        default: 0,
+        min: 0,
+        max: 1,
+        help: "Whether part 2 shows the tree, as a PBM image",
+    },
+];
+
+pub struct Day14 {
+    bots: Bots,
+    size: Point<i16>,
+    seconds: u32,
+    image: bool,
+}
+
+impl Default for Day14 {
+    fn default() -> Self {
+        Self {
+            bots: Bots::new(),
+            size: SIZE,
+            seconds: 100,
+            image: false,
+        }
+    }
+}
+
+impl Solution for Day14 {
+    fn params() -> &'static [Param] {
+        PARAMS
+    }
+
+    fn configure(&mut self, params: &Params) {
+        self.size = Point::new(params.get("width"), params.get("height"));
+        self.seconds = params.get("seconds");
+        self.image = params.get::<u8>("image") == 1;
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+
+        let nums: Vec<i16, 4> = line
+            .split([' ', ',', '='])
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        self.bots
+            .push(Bot {
+                pos: Point::new(nums[0], nums[1]),
+                vel: Point::new(nums[2], nums[3]),
+            })
+            .unwrap();
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        let bots = self
+            .bots
+            .iter()
+            .map(|bot| bot.position_after(self.seconds, self.size));
+        safety_factor(bots, self.size).into()
+    }
+
+    /// The robots form a tree when they're bunched up the closest. Their x positions
+    /// repeat every `width` seconds and their y positions every `height` seconds, so
+    /// the most bunched up second along each axis gives the tree's second, modulo the
+    /// axis. The Chinese remainder theorem puts the two together.
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        let size = self.size;
+        let x = (0..size.x as u32).min_by_key(|&t| self.spread(t, |pos| pos.x));
+        let y = (0..size.y as u32).min_by_key(|&t| self.spread(t, |pos| pos.y));
+        let (Some(x), Some(y)) = (x, y) else {
+            return Answer::Unsolved;
+        };
+        let Some((seconds, _)) = num::crt(&[(x, size.x as u32), (y, size.y as u32)]) else {
+            return Answer::Unsolved;
+        };
+
+        if self.image {
+            let mut frame = Frame::with_size(size.x as usize, size.y as usize);
+            for bot in &self.bots {
+                let pos = bot.position_after(seconds, size);
+                frame.insert(Pos::new(pos.x as isize, pos.y as isize));
+            }
+            let _ = write!(out, "{}", frame.pbm());
+        }
+        seconds.into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "bots",
+            peak: self.bots.len(),
+            capacity: self.bots.capacity(),
+        });
+    }
+}
+
+impl Day14 {
+    /// How spread out the robots are along an axis after `seconds`: the variance of
+    /// their positions, times the number of robots squared to stay in integers
+    fn spread(&self, seconds: u32, axis: impl Fn(Point<i16>) -> i16) -> i64 {
+        let (mut sum, mut squares) = (0i64, 0i64);
+        for bot in &self.bots {
+            let pos = axis(bot.position_after(seconds, self.size)) as i64;
+            sum += pos;
+            squares += pos * pos;
+        }
+        self.bots.len() as i64 * squares - sum * sum
+    }
+}
+
+#[derive(Debug)]
+struct Bot {
+    pos: Point<i16>,
+    vel: Point<i16>,
+}
+
+impl Bot {
+    /// Where the bot is after walking for `seconds`, without moving it
+    fn position_after(&self, seconds: u32, size: Point<i16>) -> Point<i16> {
+        // The walk repeats itself every `size` seconds along each axis
+        let vel = self.vel.wrap(size);
+        let steps = |len: i16| (seconds % len as u32) as i16;
+        let moved = Point::new(vel.x * steps(size.x), vel.y * steps(size.y));
+        self.pos.wrapping_add(moved, size)
+    }
+}
+
+fn safety_factor(bots: impl Iterator<Item = Point<i16>>, size: Point<i16>) -> u64 {
+    let mut quad_counts = [0u64; 4];
+
+    let q1x = size.x / 2;
+    let q2x = (size.x + 1) / 2;
+
+    let q1y = size.y / 2;
+    let q2y = (size.y + 1) / 2;
+
+    for pos in bots {
+        if pos.x < q1x && pos.y < q1y {
+            quad_counts[0] += 1;
+        } else if pos.x >= q2x && pos.y < q1y {
+            quad_counts[1] += 1;
+        } else if pos.x < q1x && pos.y >= q2y {
+            quad_counts[2] += 1;
+        } else if pos.x >= q2x && pos.y >= q2y {
+            quad_counts[3] += 1;
+        }
+    }
+
+    quad_counts.iter().product()
+}
@@ -0,0 +1,195 @@
+#![no_std]
+use aoc_common::params::Params;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::Vec;
+
+type Map = Vec<Vec<u8, 100>, 50>;
+type Coord = (i8, i8);
+
+/// A warehouse, and where the robot is in it
+struct Warehouse {
+    map: Map,
+    pos: Coord,
+    /// Whether the robot is moved around in this one
+    active: bool,
+}
+
+impl Default for Warehouse {
+    fn default() -> Self {
+        Self {
+            map: Map::new(),
+            pos: (0, 0),
+            active: true,
+        }
+    }
+}
+
+impl Warehouse {
+    fn walk(&mut self, dir: u8) {
+        if let Some(new_pos) = maybe_move(&mut self.map, &self.pos, b'@', dir) {
+            self.pos = new_pos;
+        }
+    }
+
+    fn gps_sum(&self) -> Answer {
+        match self.active {
+            true => gps_sum(&self.map).into(),
+            false => Answer::Unsolved,
+        }
+    }
+}
+
+/// The robot walks around the warehouse of part 1 and the twice as wide one of part 2
+/// at the same time, unless only one part is asked for
+pub struct Day15 {
+    narrow: Warehouse,
+    wide: Warehouse,
+    reading_map: bool,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Self {
+            narrow: Warehouse::default(),
+            wide: Warehouse::default(),
+            reading_map: true,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    fn configure(&mut self, params: &Params) {
+        self.narrow.active = params.wants(1);
+        self.wide.active = params.wants(2);
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        if self.reading_map {
+            if line.is_empty() {
+                self.reading_map = false;
+                self.narrow.pos = find_bot(&self.narrow.map);
+                self.wide.pos = find_bot(&self.wide.map);
+            } else {
+                let mut row = Vec::new();
+                for c in line {
+                    match c {
+                        b'#' => row.extend_from_slice(b"##").unwrap(),
+                        b'O' => row.extend_from_slice(b"[]").unwrap(),
+                        b'.' => row.extend_from_slice(b"..").unwrap(),
+                        b'@' => row.extend_from_slice(b"@.").unwrap(),
+                        x => row.push(*x).unwrap(),
+                    };
+                }
+                self.wide.map.push(row).unwrap();
+                self.narrow
+                    .map
+                    .push(Vec::from_slice(line).unwrap())
+                    .unwrap();
+            }
+        } else {
+            for &dir in line {
+                for warehouse in [&mut self.narrow, &mut self.wide] {
+                    if warehouse.active {
+                        warehouse.walk(dir);
+                    }
+                }
+            }
+        }
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.narrow.gps_sum()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        self.wide.gps_sum()
+    }
+}
+
+fn find_bot(map: &Map) -> Coord {
+    for (y, row) in map.iter().enumerate() {
+        if let Some(x) = row.iter().position(|b| *b == b'@') {
+            return (x as i8, y as i8);
+        }
+    }
+    (0, 0)
+}
+
+fn maybe_move(map: &mut Map, pos: &Coord, marker: u8, dir: u8) -> Option<Coord> {
+    let (dx, dy) = match dir {
+        b'^' => (0, -1),
+        b'>' => (1, 0),
+        b'v' => (0, 1),
+        b'<' => (-1, 0),
+        _ => unreachable!(),
+    };
+
+    if can_move(map, pos, dx, dy) {
+        Some(do_move(map, pos, marker, dx, dy, false))
+    } else {
+        None
+    }
+}
+
+fn can_move(map: &Map, pos: &Coord, dx: i8, dy: i8) -> bool {
+    let nx = pos.0 + dx;
+    let ny = pos.1 + dy;
+
+    let target = map[ny as usize][nx as usize];
+
+    match target {
+        b'#' => false,
+        b'.' => true,
+        b'O' => can_move(map, &(nx, ny), dx, dy),
+        b'[' | b']' if dy == 0 => can_move(map, &(nx, ny), dx, dy),
+        b'[' => can_move(map, &(nx, ny), dx, dy) && can_move(map, &(nx + 1, ny), dx, dy),
+        b']' => can_move(map, &(nx - 1, ny), dx, dy) && can_move(map, &(nx, ny), dx, dy),
+        _ => unreachable!(),
+    }
+}
+
+fn do_move(map: &mut Map, pos: &Coord, marker: u8, dx: i8, dy: i8, is_child: bool) -> Coord {
+    let nx = pos.0 + dx;
+    let ny = pos.1 + dy;
+
+    let target = map[ny as usize][nx as usize];
+
+    // Move target first:
+    if target == b'O' || target == b'[' || target == b']' {
+        do_move(map, &(nx, ny), target, dx, dy, false);
+    }
+
+    // Move wide boxes together when moving up or down
+    if !is_child && dy != 0 {
+        if marker == b'[' {
+            do_move(map, &(pos.0 + 1, pos.1), b']', dx, dy, true);
+        } else if marker == b']' {
+            do_move(map, &(pos.0 - 1, pos.1), b'[', dx, dy, true);
+        }
+    }
+
+    // Move ourself
+    map[ny as usize][nx as usize] = marker;
+    map[pos.1 as usize][pos.0 as usize] = b'.';
+    (nx, ny)
+}
+
+fn gps_sum(map: &Map) -> u64 {
+    let mut sum = 0;
+    for (y, row) in map.iter().enumerate() {
+        sum += row
+            .iter()
+            .enumerate()
+            .filter_map(|(x, c)| {
+                if *c == b'O' || *c == b'[' {
+                    Some(x)
+                } else {
+                    None
+                }
+            })
+            .map(|x| 100 * y + x)
+            .sum::<usize>() as u64;
+    }
+    sum
+}
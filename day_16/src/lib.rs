@@ -0,0 +1,169 @@
+#![no_std]
+use aoc_common::bits::{words, BitSet};
+use aoc_common::geom::{Dir4 as Dir, Point};
+use aoc_common::memory::Usage;
+use aoc_common::path::{self, Search};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::{self, Display, Write};
+use heapless::Vec;
+
+const MAP_SIZE: usize = 141;
+const TILES: usize = MAP_SIZE * MAP_SIZE;
+type Map = Vec<Vec<u8, MAP_SIZE>, MAP_SIZE>;
+type Coord = Point<i16>;
+/// The reindeer's position and the direction it's facing
+type State = (Coord, Dir);
+/// The distances are kept in 16 bits each, a step never costs more than a turn
+type Scores = Search<State, u32, { TILES * 4 }, 512, u16>;
+
+/// Why there's no answer
+#[derive(Debug, Clone, Copy)]
+enum Error {
+    /// The map has no tile with this mark
+    Missing(char),
+    Search(path::Error),
+    /// The end can't be reached from the start
+    NoPath,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing(mark) => write!(f, "The map has no {mark}"),
+            Error::Search(e) => write!(f, "{e}"),
+            Error::NoPath => f.write_str("There's no path from S to E"),
+        }
+    }
+}
+
+pub struct Day16 {
+    map: Map,
+    /// The scores part 1 found, which part 2 walks back over
+    scores: Scores,
+    /// The end and the lowest score to reach it, once part 1 searched
+    lowest: Result<(Coord, u32), Error>,
+}
+
+impl Default for Day16 {
+    fn default() -> Self {
+        Self {
+            map: Map::new(),
+            scores: Scores::new(),
+            lowest: Err(Error::NoPath),
+        }
+    }
+}
+
+impl Solution for Day16 {
+    fn parse_line(&mut self, line: &[u8]) {
+        self.map.push(Vec::from_slice(line).unwrap()).unwrap();
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        self.lowest = lowest_score(&mut self.scores, &self.map);
+        report(self.lowest.map(|(_, score)| score), out)
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        let tiles = self
+            .lowest
+            .and_then(|(end, _)| count_tiles(&mut self.scores, &self.map, end));
+        report(tiles, out)
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "map",
+            peak: self.map.len(),
+            capacity: self.map.capacity(),
+        });
+    }
+}
+
+/// The answer, or why there's none
+fn report(answer: Result<u32, Error>, out: &mut dyn Write) -> Answer {
+    match answer {
+        Ok(answer) => answer.into(),
+        Err(e) => {
+            let _ = writeln!(out, "{e}");
+            Answer::Unsolved
+        }
+    }
+}
+
+fn find_on_map(map: &Map, needle: u8) -> Option<Coord> {
+    for (y, row) in map.iter().enumerate() {
+        if let Some(x) = row.iter().position(|c| *c == needle) {
+            return Some(Coord::new(x as i16, y as i16));
+        }
+    }
+    None
+}
+
+fn tile(pos: &Coord) -> usize {
+    pos.y as usize * MAP_SIZE + pos.x as usize
+}
+
+fn index((pos, dir): &State) -> usize {
+    tile(pos) * 4 + dir.idx()
+}
+
+fn is_open(map: &Map, pos: Coord) -> bool {
+    let (Ok(x), Ok(y)) = (usize::try_from(pos.x), usize::try_from(pos.y)) else {
+        return false;
+    };
+    map.get(y)
+        .and_then(|row| row.get(x))
+        .is_some_and(|&c| c != b'#')
+}
+
+/// Steps forward, or turns left or right on the spot
+fn moves(map: &Map, &(pos, dir): &State, f: &mut dyn FnMut(State, u32)) {
+    if is_open(map, pos.step(dir)) {
+        f((pos.step(dir), dir), 1);
+    }
+    f((pos, dir.turn_left()), 1000);
+    f((pos, dir.turn_right()), 1000);
+}
+
+/// The moves in reverse: where the reindeer could have come from
+fn unmoves(map: &Map, &(pos, dir): &State, f: &mut dyn FnMut(State, u32)) {
+    if is_open(map, pos.step(dir.opposite())) {
+        f((pos.step(dir.opposite()), dir), 1);
+    }
+    f((pos, dir.turn_left()), 1000);
+    f((pos, dir.turn_right()), 1000);
+}
+
+/// Finds the lowest score from the start to the end, and returns the end with it
+fn lowest_score(scores: &mut Scores, map: &Map) -> Result<(Coord, u32), Error> {
+    let start = find_on_map(map, b'S').ok_or(Error::Missing('S'))?;
+    let end = find_on_map(map, b'E').ok_or(Error::Missing('E'))?;
+    let found = scores
+        .dijkstra(
+            &[(start, Dir::E)],
+            index,
+            |state, f| moves(map, state, f),
+            |(pos, _)| *pos == end,
+        )
+        .map_err(Error::Search)?;
+    found.map(|(_, score)| (end, score)).ok_or(Error::NoPath)
+}
+
+/// Returns the number of tiles that are part of any shortest path from start to end,
+/// from the scores of the search for the lowest score. The paths are found again from
+/// the scores, so no predecessors are kept.
+fn count_tiles(scores: &mut Scores, map: &Map, end: Coord) -> Result<u32, Error> {
+    let mut visited: BitSet<{ words(TILES) }> = BitSet::new();
+    scores
+        .on_shortest_paths(
+            &Dir::ALL.map(|dir| (end, dir)),
+            index,
+            |state, f| unmoves(map, state, f),
+            |(pos, _), _| {
+                visited.insert(tile(pos));
+            },
+        )
+        .map_err(Error::Search)?;
+    Ok(visited.len() as u32)
+}
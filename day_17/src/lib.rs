@@ -0,0 +1,173 @@
+#![no_std]
+use aoc_common::params::{Param, Params};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::{self, Display, Write};
+use heapless::String;
+
+mod quine;
+mod vm;
+
+use vm::{Program, Vm};
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "disasm",
+        default: 0,
+        min: 0,
+        max: 1,
+        help: "Whether part 1 lists the program first",
+    },
+    Param {
+        name: "trace",
+        default: 0,
+        min: 0,
+        max: 1,
+        help: "Whether part 1 shows every instruction it runs, with the registers",
+    },
+    Param {
+        name: "steps",
+        default: 100_000,
+        min: 1,
+        max: 100_000_000,
+        help: "How many instructions a run may take before it's stopped",
+    },
+];
+
+/// Why the input couldn't be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseError {
+    /// The line isn't like `Register A: 729`
+    Invalid { line: usize },
+    /// A value isn't a number, or is too big for its register or the program
+    Number { line: usize },
+    /// The program has more values than a [`Program`] holds
+    TooLong,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Invalid { line } => write!(f, "Line {line} isn't like `Name: value`"),
+            ParseError::Number { line } => write!(f, "Line {line} has an invalid value"),
+            ParseError::TooLong => write!(
+                f,
+                "The program is longer than {} values",
+                Program::new().capacity()
+            ),
+        }
+    }
+}
+
+pub struct Day17 {
+    read_idx: usize,
+    /// The first line that couldn't be read
+    error: Option<ParseError>,
+    reg: [u64; 3],
+    program: Program,
+    disasm: bool,
+    trace: bool,
+    limit: usize,
+}
+
+impl Default for Day17 {
+    fn default() -> Self {
+        Self {
+            read_idx: 0,
+            error: None,
+            reg: [0; 3],
+            program: Program::new(),
+            disasm: false,
+            trace: false,
+            limit: 100_000,
+        }
+    }
+}
+
+impl Solution for Day17 {
+    fn params() -> &'static [Param] {
+        PARAMS
+    }
+
+    fn configure(&mut self, params: &Params) {
+        self.disasm = params.get::<u8>("disasm") == 1;
+        self.trace = params.get::<u8>("trace") == 1;
+        self.limit = params.get("steps");
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        if let Err(e) = self.parse(line) {
+            self.error.get_or_insert(e);
+        }
+        self.read_idx += 1;
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        if let Some(e) = self.error {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+        if self.disasm {
+            let _ = vm::disassemble(&self.program, out);
+        }
+
+        let mut vm = Vm::new(self.reg);
+        let trace: Option<&mut dyn Write> = match self.trace {
+            true => Some(&mut *out),
+            false => None,
+        };
+        if let Err(e) = vm.run(&self.program, self.limit, trace) {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+
+        let mut res = String::new();
+        for (i, o) in vm.out.iter().enumerate() {
+            if i > 0 {
+                res.push(',').unwrap();
+            }
+            write!(res, "{o}").unwrap();
+        }
+        Answer::Text(res)
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        if let Some(e) = self.error {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+        match quine::find(&self.program, self.reg, self.limit) {
+            Ok(a) => a.into(),
+            Err(e) => {
+                let _ = writeln!(out, "{e}");
+                Answer::Unsolved
+            }
+        }
+    }
+}
+
+impl Day17 {
+    /// Reads a register, or the program
+    fn parse(&mut self, line: &[u8]) -> Result<(), ParseError> {
+        let line_no = self.read_idx + 1;
+        let value = || {
+            core::str::from_utf8(line)
+                .ok()
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, value)| value.trim())
+                .ok_or(ParseError::Invalid { line: line_no })
+        };
+        let invalid = |_| ParseError::Number { line: line_no };
+
+        match self.read_idx {
+            0..=2 => self.reg[self.read_idx] = value()?.parse().map_err(invalid)?,
+            4 => {
+                for value in value()?.split(',') {
+                    let value = value.trim().parse().map_err(invalid)?;
+                    self.program.push(value).map_err(|_| ParseError::TooLong)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
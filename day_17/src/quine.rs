@@ -0,0 +1,184 @@
+//! Finding the smallest A that makes a program output itself.
+//!
+//! This works for programs that are a single loop, which outputs one value and shifts A
+//! right by a fixed number of bits each time around, until A is 0. The value output
+//! then only depends on A at the start of the iteration, so the last value only depends
+//! on the top bits of A, the one before on the bits below those, and so on. A is built
+//! up from its top bits, trying the smallest bits first and backtracking when the
+//! output stops matching the end of the program. The program is checked for that shape
+//! first, and the search reports which assumption doesn't hold.
+use core::fmt::{self, Display};
+
+use crate::vm::{Instr, Opcode, Vm, VmError, A, B, C};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The program has an odd length
+    Truncated,
+    /// The program has an instruction the computer can't run
+    Invalid(VmError),
+    /// The program doesn't end with its only jump, back to the start
+    NotALoop,
+    /// The loop doesn't shift A right by 1 to 3 bits, exactly once
+    NoShift,
+    /// The loop doesn't output exactly once
+    Outputs(usize),
+    /// The loop reads B or C before it writes it, so it depends on earlier iterations
+    Carries(char),
+    /// No A makes the program output itself
+    NotFound,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Truncated => f.write_str("The last instruction has no operand"),
+            Error::Invalid(e) => write!(f, "{e}"),
+            Error::NotALoop => f.write_str("The program isn't a single loop ending in jnz 0"),
+            Error::NoShift => f.write_str("The loop doesn't shift A by a fixed 1 to 3 bits"),
+            Error::Outputs(n) => write!(f, "The loop outputs {n} times instead of once"),
+            Error::Carries(reg) => write!(f, "The loop reads {reg} before writing it"),
+            Error::NotFound => f.write_str("No A makes the program output itself"),
+        }
+    }
+}
+
+/// Finds the smallest A that makes the program output itself, starting with the given
+/// B and C. Every run it tries is stopped after `limit` instructions.
+pub fn find(program: &[u8], reg: [u64; 3], limit: usize) -> Result<u64, Error> {
+    let shift = check(program)?;
+    search(program, reg, shift, program.len(), 0, limit).ok_or(Error::NotFound)
+}
+
+/// Checks that the program has the shape the search needs, see the module's docs,
+/// and returns how many bits A is shifted by each iteration
+fn check(program: &[u8]) -> Result<u32, Error> {
+    if program.len() % 2 == 1 {
+        return Err(Error::Truncated);
+    }
+    let mut body = (0..program.len())
+        .step_by(2)
+        .filter_map(|ip| Instr::decode(program, ip));
+
+    let (mut jumps, mut shifts, mut outputs) = (0, 0, 0);
+    let mut shift = 0;
+    // Which of B and C were written in this iteration yet
+    let mut written = [false; 3];
+    for instr in body.clone() {
+        let instr = instr.map_err(Error::Invalid)?;
+        let combo = match instr.op.takes_combo() {
+            true => instr.combo().register(),
+            false => None,
+        };
+        let reads: &[usize] = match instr.op {
+            Opcode::Bxl => &[B],
+            Opcode::Bxc => &[B, C],
+            _ => &[],
+        };
+        for reg in reads.iter().copied().chain(combo) {
+            if reg != A && !written[reg] {
+                return Err(Error::Carries(['A', 'B', 'C'][reg]));
+            }
+        }
+
+        match instr.op {
+            Opcode::Adv => {
+                shifts += 1;
+                shift = instr.operand as u32;
+            }
+            Opcode::Jnz => jumps += 1,
+            Opcode::Out => outputs += 1,
+            Opcode::Bxl | Opcode::Bst | Opcode::Bxc | Opcode::Bdv => written[B] = true,
+            Opcode::Cdv => written[C] = true,
+        }
+    }
+
+    let looped = matches!(
+        body.next_back(),
+        Some(Ok(Instr {
+            op: Opcode::Jnz,
+            operand: 0
+        }))
+    );
+    if jumps != 1 || !looped {
+        return Err(Error::NotALoop);
+    }
+    if shifts != 1 || !(1..=3).contains(&shift) {
+        return Err(Error::NoShift);
+    }
+    if outputs != 1 {
+        return Err(Error::Outputs(outputs));
+    }
+    Ok(shift)
+}
+
+/// Finds the smallest A that makes the program output itself, given the top bits in
+/// `a` that already make it output all but its first `left` values
+fn search(
+    program: &[u8],
+    reg: [u64; 3],
+    shift: u32,
+    left: usize,
+    a: u64,
+    limit: usize,
+) -> Option<u64> {
+    if left == 0 {
+        return Some(a);
+    }
+    for bits in 0..1 << shift {
+        let candidate = a << shift | bits;
+        let mut vm = Vm::new([candidate, reg[B], reg[C]]);
+        if vm.run(program, limit, None).is_ok() && vm.out == program[left - 1..] {
+            if let Some(a) = search(program, reg, shift, left - 1, candidate, limit) {
+                return Some(a);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: usize = 1000;
+
+    #[test]
+    fn test_find() {
+        // A = A >> 3; output A % 8; loop
+        let program = [0, 3, 5, 4, 3, 0];
+        assert_eq!(find(&program, [2024, 0, 0], LIMIT), Ok(117_440));
+    }
+
+    #[test]
+    fn test_rejected_shapes() {
+        assert_eq!(check(&[0, 3, 5]), Err(Error::Truncated));
+        assert_eq!(
+            check(&[8, 3, 3, 0]),
+            Err(Error::Invalid(VmError::InvalidOpcode { ip: 0, code: 8 }))
+        );
+        // No jump back at the end
+        assert_eq!(check(&[0, 3, 5, 4]), Err(Error::NotALoop));
+        assert_eq!(check(&[0, 3, 3, 0, 5, 4]), Err(Error::NotALoop));
+        assert_eq!(check(&[0, 3, 3, 2, 5, 4, 3, 0]), Err(Error::NotALoop));
+        // Shifting by A, and by nothing at all
+        assert_eq!(check(&[0, 4, 5, 4, 3, 0]), Err(Error::NoShift));
+        assert_eq!(check(&[5, 4, 3, 0]), Err(Error::NoShift));
+        assert_eq!(check(&[0, 3, 5, 4, 5, 4, 3, 0]), Err(Error::Outputs(2)));
+        assert_eq!(check(&[0, 3, 3, 0]), Err(Error::Outputs(0)));
+        // B and C are left over from the last iteration
+        assert_eq!(check(&[0, 3, 5, 5, 3, 0]), Err(Error::Carries('B')));
+        assert_eq!(
+            check(&[0, 3, 2, 4, 4, 0, 5, 5, 3, 0]),
+            Err(Error::Carries('C'))
+        );
+    }
+
+    #[test]
+    fn test_not_found() {
+        // Always outputs 0, so never 1
+        let program = [0, 1, 2, 0, 5, 5, 3, 0];
+        assert_eq!(check(&program), Ok(1));
+        assert_eq!(find(&program, [0; 3], LIMIT), Err(Error::NotFound));
+    }
+}
@@ -0,0 +1,352 @@
+//! The 3-bit computer: decoding its instructions, listing them, and running them.
+//!
+//! Any program runs without panicking, whatever it's made of: what the computer can't
+//! make sense of stops it with a [`VmError`], and so does running for too long.
+use core::fmt::{self, Display, Write};
+use heapless::{String, Vec};
+
+pub type Program = Vec<u8, 16>;
+/// What a program outputs, as many values as fit in an answer
+pub type Output = Vec<u8, 32>;
+
+pub const A: usize = 0;
+pub const B: usize = 1;
+pub const C: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmError {
+    /// There's no instruction with this opcode
+    InvalidOpcode { ip: usize, code: u8 },
+    /// The instruction uses combo operand 7, which is reserved
+    ReservedCombo { ip: usize },
+    /// The program output more than an [`Output`] holds
+    OutputFull,
+    /// The program didn't halt within this many instructions
+    StepLimit(usize),
+    /// A jump went to an odd address, in the middle of an instruction
+    Misaligned { ip: usize, target: u8 },
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::InvalidOpcode { ip, code } => write!(f, "Invalid opcode {code} at {ip}"),
+            VmError::ReservedCombo { ip } => write!(f, "Reserved combo operand 7 at {ip}"),
+            VmError::OutputFull => {
+                write!(f, "More than {} values output", Output::new().capacity())
+            }
+            VmError::StepLimit(limit) => write!(f, "Stopped after {limit} instructions"),
+            VmError::Misaligned { ip, target } => write!(f, "Jump to odd address {target} at {ip}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    pub fn from_u8(code: u8) -> Option<Self> {
+        use Opcode::*;
+        [Adv, Bxl, Bst, Jnz, Bxc, Out, Bdv, Cdv]
+            .get(code as usize)
+            .copied()
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand is a combo operand, rather than a literal one
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+}
+
+/// A combo operand: 0 to 3 are literals, 4 to 6 registers A to C, and 7 is reserved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Combo(pub u8);
+
+impl Combo {
+    /// The register it reads, if any
+    pub fn register(self) -> Option<usize> {
+        match self.0 {
+            4..=6 => Some((self.0 - 4) as usize),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            l @ 0..=3 => write!(f, "{l}"),
+            4 => f.write_str("A"),
+            5 => f.write_str("B"),
+            6 => f.write_str("C"),
+            x => write!(f, "<{x}>"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instr {
+    pub op: Opcode,
+    pub operand: u8,
+}
+
+impl Instr {
+    /// Decodes the instruction at `ip`, None when there's no whole instruction there
+    pub fn decode(program: &[u8], ip: usize) -> Option<Result<Self, VmError>> {
+        let (&code, &operand) = (program.get(ip)?, program.get(ip + 1)?);
+        let Some(op) = Opcode::from_u8(code) else {
+            return Some(Err(VmError::InvalidOpcode { ip, code }));
+        };
+        Some(Ok(Self { op, operand }))
+    }
+
+    pub fn combo(self) -> Combo {
+        Combo(self.operand)
+    }
+
+    /// What the instruction does, like `B = A % 8`
+    pub fn pseudo(self) -> Pseudo {
+        Pseudo(self)
+    }
+}
+
+/// The mnemonic and operand, like `bst A`
+impl Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.op.mnemonic();
+        match self.op {
+            // The operand is ignored
+            Opcode::Bxc => f.pad(mnemonic),
+            _ if self.op.takes_combo() => f.pad(&with_operand(mnemonic, self.combo())),
+            _ => f.pad(&with_operand(mnemonic, self.operand)),
+        }
+    }
+}
+
+fn with_operand(mnemonic: &str, operand: impl Display) -> String<8> {
+    let mut s = String::new();
+    let _ = write!(s, "{mnemonic} {operand}");
+    s
+}
+
+/// Shows what an [`Instr`] does
+pub struct Pseudo(Instr);
+
+impl Display for Pseudo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (combo, literal) = (self.0.combo(), self.0.operand);
+        match self.0.op {
+            Opcode::Adv => write!(f, "A = A >> {combo}"),
+            Opcode::Bxl => write!(f, "B = B ^ {literal}"),
+            Opcode::Bst => write!(f, "B = {combo} % 8"),
+            Opcode::Jnz => write!(f, "if A != 0 jump to {literal}"),
+            Opcode::Bxc => f.write_str("B = B ^ C"),
+            Opcode::Out => write!(f, "output {combo} % 8"),
+            Opcode::Bdv => write!(f, "B = A >> {combo}"),
+            Opcode::Cdv => write!(f, "C = A >> {combo}"),
+        }
+    }
+}
+
+/// Lists the program, an instruction per line with its address and what it does
+pub fn disassemble(program: &[u8], out: &mut dyn Write) -> fmt::Result {
+    let mut ip = 0;
+    while let Some(instr) = Instr::decode(program, ip) {
+        match instr {
+            Ok(instr) => writeln!(out, "{ip:02}  {instr:<6}  ; {}", instr.pseudo())?,
+            Err(e) => writeln!(out, "{ip:02}  ???     ; {e}")?,
+        }
+        ip += 2;
+    }
+    if ip < program.len() {
+        writeln!(out, "{ip:02}  {}       ; no operand, halts", program[ip])?;
+    }
+    Ok(())
+}
+
+pub struct Vm {
+    pub ip: usize,
+    pub reg: [u64; 3],
+    pub out: Output,
+}
+
+impl Vm {
+    pub fn new(reg: [u64; 3]) -> Self {
+        Self {
+            ip: 0,
+            reg,
+            out: Vec::new(),
+        }
+    }
+
+    /// Runs the program until it halts, or until it ran `limit` instructions.
+    /// Returns how many instructions it ran.
+    /// With a `trace`, every instruction is written to it with the registers before it.
+    pub fn run(
+        &mut self,
+        program: &[u8],
+        limit: usize,
+        mut trace: Option<&mut dyn Write>,
+    ) -> Result<usize, VmError> {
+        for steps in 0..=limit {
+            let Some(instr) = Instr::decode(program, self.ip) else {
+                return Ok(steps);
+            };
+            if steps == limit {
+                break;
+            }
+            let instr = instr?;
+            if let Some(trace) = trace.as_deref_mut() {
+                let [a, b, c] = self.reg;
+                let _ = writeln!(trace, "{:02}  {instr:<6}  A={a} B={b} C={c}", self.ip);
+            }
+            self.exec(instr)?;
+        }
+        Err(VmError::StepLimit(limit))
+    }
+
+    fn exec(&mut self, instr: Instr) -> Result<(), VmError> {
+        match instr.op {
+            Opcode::Adv => self.reg[A] = self.div(instr.combo())?,
+            Opcode::Bxl => self.reg[B] ^= instr.operand as u64,
+            Opcode::Bst => self.reg[B] = self.combo(instr.combo())? % 8,
+            Opcode::Jnz if self.reg[A] != 0 => {
+                let target = instr.operand;
+                if target % 2 == 1 {
+                    return Err(VmError::Misaligned {
+                        ip: self.ip,
+                        target,
+                    });
+                }
+                self.ip = target as usize;
+                return Ok(());
+            }
+            Opcode::Jnz => {}
+            Opcode::Bxc => self.reg[B] ^= self.reg[C],
+            Opcode::Out => {
+                let res = self.combo(instr.combo())? % 8;
+                self.out.push(res as u8).map_err(|_| VmError::OutputFull)?;
+            }
+            Opcode::Bdv => self.reg[B] = self.div(instr.combo())?,
+            Opcode::Cdv => self.reg[C] = self.div(instr.combo())?,
+        }
+        self.ip += 2;
+        Ok(())
+    }
+
+    fn combo(&self, operand: Combo) -> Result<u64, VmError> {
+        match operand.0 {
+            l @ 0..=3 => Ok(l as u64),
+            4..=6 => Ok(self.reg[(operand.0 - 4) as usize]),
+            _ => Err(VmError::ReservedCombo { ip: self.ip }),
+        }
+    }
+
+    /// A divided by 2 to the power of the operand, which is 0 once that's more than
+    /// all of A's bits, rather than overflowing
+    fn div(&self, operand: Combo) -> Result<u64, VmError> {
+        let shift = self.combo(operand)?;
+        Ok(u32::try_from(shift)
+            .ok()
+            .and_then(|shift| self.reg[A].checked_shr(shift))
+            .unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(program: &[u8]) -> String<256> {
+        let mut s = String::new();
+        disassemble(program, &mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            listing(&[2, 4, 1, 1, 7, 5, 4, 0, 0, 3, 5, 5, 3, 0]),
+            "00  bst A   ; B = A % 8\n\
+             02  bxl 1   ; B = B ^ 1\n\
+             04  cdv B   ; C = A >> B\n\
+             06  bxc     ; B = B ^ C\n\
+             08  adv 3   ; A = A >> 3\n\
+             10  out B   ; output B % 8\n\
+             12  jnz 0   ; if A != 0 jump to 0\n"
+        );
+        assert_eq!(
+            listing(&[8, 1, 5, 7, 3]),
+            "00  ???     ; Invalid opcode 8 at 0\n\
+             02  out <7>  ; output <7> % 8\n\
+             04  3       ; no operand, halts\n"
+        );
+    }
+
+    fn run(program: &[u8], reg: [u64; 3], limit: usize) -> (Result<usize, VmError>, Vm) {
+        let mut vm = Vm::new(reg);
+        (vm.run(program, limit, None), vm)
+    }
+
+    #[test]
+    fn test_errors() {
+        let (result, _) = run(&[0, 1, 8, 0], [8, 0, 0], 10);
+        assert_eq!(result, Err(VmError::InvalidOpcode { ip: 2, code: 8 }));
+        let (result, _) = run(&[5, 7], [0; 3], 10);
+        assert_eq!(result, Err(VmError::ReservedCombo { ip: 0 }));
+        // Outputs A forever
+        let (result, vm) = run(&[5, 4, 3, 0], [1, 0, 0], 1000);
+        assert_eq!(result, Err(VmError::OutputFull));
+        assert_eq!(vm.out.len(), vm.out.capacity());
+        let (result, _) = run(&[3, 1], [1, 0, 0], 10);
+        assert_eq!(result, Err(VmError::Misaligned { ip: 0, target: 1 }));
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let program = [0, 1, 0, 1];
+        assert_eq!(run(&program, [8, 0, 0], 2).0, Ok(2));
+        assert_eq!(run(&program, [8, 0, 0], 1).0, Err(VmError::StepLimit(1)));
+        let (result, vm) = run(&[3, 0], [1, 0, 0], 10);
+        assert_eq!(result, Err(VmError::StepLimit(10)));
+        assert_eq!(vm.ip, 0);
+    }
+
+    #[test]
+    fn test_large_shifts() {
+        // A = A >> B, B = A >> C and C = A >> B, by 63 bits and by more than A has
+        for (shift, expected) in [(63, 1), (64, 0), (u64::from(u32::MAX) + 1, 0)] {
+            let (_, vm) = run(&[0, 5], [u64::MAX, shift, 0], 10);
+            assert_eq!(vm.reg[A], expected);
+            let (_, vm) = run(&[6, 6], [u64::MAX, 0, shift], 10);
+            assert_eq!(vm.reg[B], expected);
+            let (_, vm) = run(&[7, 5], [u64::MAX, shift, 0], 10);
+            assert_eq!(vm.reg[C], expected);
+        }
+    }
+}
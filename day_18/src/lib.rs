@@ -0,0 +1,216 @@
+#![no_std]
+use aoc_common::bits::{words, BitGrid};
+use aoc_common::grid::Pos;
+use aoc_common::memory::Usage;
+use aoc_common::params::{Param, Params};
+use aoc_common::path::{self, Search};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::{self, Display, Write};
+use heapless::{String, Vec};
+
+const MAP_SIZE: usize = 71;
+const BLOCKS_TO_DROP: usize = 1024;
+/// Which bytes are corrupted
+type Map = BitGrid<{ words(MAP_SIZE) }, MAP_SIZE>;
+type Coord = (i16, i16);
+const MAX_DROPS: usize = 4096;
+type Drops = Vec<Coord, MAX_DROPS>;
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "size",
+        default: MAP_SIZE as i64,
+        min: 1,
+        max: MAP_SIZE as i64,
+        help: "The width and height of the memory space",
+    },
+    Param {
+        name: "bytes",
+        default: BLOCKS_TO_DROP as i64,
+        min: 0,
+        max: MAX_DROPS as i64,
+        help: "How many bytes have fallen for part 1",
+    },
+];
+
+const START: Coord = (0, 0);
+
+/// Why there's no answer
+#[derive(Debug, Clone, Copy)]
+enum Error {
+    /// The line isn't like `6,1`
+    Invalid {
+        line: usize,
+    },
+    /// The byte falls outside the memory space
+    Outside {
+        line: usize,
+    },
+    /// More bytes than [`MAX_DROPS`]
+    TooMany,
+    Search(path::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Invalid { line } => write!(f, "Line {line} isn't like `x,y`"),
+            Error::Outside { line } => write!(f, "Line {line} is outside the memory space"),
+            Error::TooMany => write!(f, "More than {MAX_DROPS} bytes"),
+            Error::Search(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+pub struct Day18 {
+    /// The first line that couldn't be read
+    error: Option<Error>,
+    drops: Drops,
+    size: usize,
+    bytes: usize,
+}
+
+impl Default for Day18 {
+    fn default() -> Self {
+        Self {
+            error: None,
+            drops: Drops::new(),
+            size: MAP_SIZE,
+            bytes: BLOCKS_TO_DROP,
+        }
+    }
+}
+
+impl Solution for Day18 {
+    fn params() -> &'static [Param] {
+        PARAMS
+    }
+
+    fn configure(&mut self, params: &Params) {
+        self.size = params.get("size");
+        self.bytes = params.get("bytes");
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.parse(line) {
+            self.error = Some(e);
+        }
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        if let Some(e) = self.error {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+        let mut map = Map::with_size(self.size, self.size);
+        for drop in self.drops.iter().take(self.bytes) {
+            map.insert(pos(drop));
+        }
+
+        match shortest_path(&map, START, self.end()) {
+            Ok(Some(steps)) => (steps as u32).into(),
+            Ok(None) => Answer::Unsolved,
+            Err(e) => {
+                let _ = writeln!(out, "{}", Error::Search(e));
+                Answer::Unsolved
+            }
+        }
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        if let Some(e) = self.error {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+        let mut map = Map::with_size(self.size, self.size);
+        for drop @ (x, y) in self.drops.iter() {
+            map.insert(pos(drop));
+
+            let found = match shortest_path(&map, START, self.end()) {
+                Ok(found) => found,
+                Err(e) => {
+                    let _ = writeln!(out, "{}", Error::Search(e));
+                    return Answer::Unsolved;
+                }
+            };
+            if found.is_none() {
+                dump_map(&map, out);
+
+                let mut blocker = String::new();
+                write!(blocker, "{x},{y}").unwrap();
+                return Answer::Text(blocker);
+            }
+        }
+
+        Answer::Unsolved
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "drops",
+            peak: self.drops.len(),
+            capacity: self.drops.capacity(),
+        });
+    }
+}
+
+impl Day18 {
+    /// Reads a byte's coordinates, which have to be inside the memory space
+    fn parse(&mut self, line: &[u8]) -> Result<(), Error> {
+        let line_no = self.drops.len() + 1;
+        let invalid = Error::Invalid { line: line_no };
+        let (x, y) = core::str::from_utf8(line)
+            .ok()
+            .and_then(|line| line.split_once(','))
+            .ok_or(invalid)?;
+        let coord = |s: &str| s.parse::<usize>().map_err(|_| invalid);
+        let (x, y) = (coord(x)?, coord(y)?);
+        if x >= self.size || y >= self.size {
+            return Err(Error::Outside { line: line_no });
+        }
+        self.drops
+            .push((x as i16, y as i16))
+            .map_err(|_| Error::TooMany)
+    }
+
+    /// The bottom right corner
+    fn end(&self) -> Coord {
+        let last = (self.size - 1) as i16;
+        (last, last)
+    }
+}
+
+fn pos(&(x, y): &Coord) -> Pos {
+    Pos::new(x as isize, y as isize)
+}
+
+fn dump_map(map: &Map, out: &mut dyn Write) {
+    for row in map.rows() {
+        for x in 0..map.width() {
+            let _ = out.write_char(if row.contains(x) { '#' } else { '.' });
+        }
+        let _ = writeln!(out);
+    }
+    let _ = writeln!(out);
+}
+
+fn shortest_path(map: &Map, start: Coord, end: Coord) -> Result<Option<u16>, path::Error> {
+    let mut search: Search<Coord, u16, { MAP_SIZE * MAP_SIZE }, 1024> = Search::new();
+    let found = search.bfs(
+        &[start],
+        |(x, y)| *y as usize * MAP_SIZE + *x as usize,
+        |(x, y), f| {
+            for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let next = (x + dx, y + dy);
+                if map.get(pos(&next)) == Some(false) {
+                    f(next);
+                }
+            }
+        },
+        |pos| *pos == end,
+    )?;
+    Ok(found.map(|(_, steps)| steps))
+}
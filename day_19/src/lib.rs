@@ -0,0 +1,96 @@
+#![no_std]
+use aoc_common::memo::{Cache as Memo, Eviction};
+use aoc_common::memory::{HighWater, Usage};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use core::str::FromStr;
+use heapless::{String, Vec};
+
+type Towel = String<8>;
+type Towels = Vec<Towel, 500>;
+
+/// The number of arrangements of the rest of the design that's being counted, by its
+/// length. Cleared for every design, so it needs an entry per character at most.
+type Cache = Memo<usize, u64, 128>;
+
+pub struct Day19 {
+    read_idx: usize,
+    towels: Towels,
+    cache: Cache,
+    /// The most arrangements counts cached for a design
+    cache_peak: HighWater,
+    /// The designs that can be made in at least one way
+    designs_made: usize,
+    arrangements: u64,
+}
+
+impl Default for Day19 {
+    fn default() -> Self {
+        Self {
+            read_idx: 0,
+            towels: Towels::new(),
+            cache: Cache::new(Eviction::Lru),
+            cache_peak: HighWater::default(),
+            designs_made: 0,
+            arrangements: 0,
+        }
+    }
+}
+
+impl Solution for Day19 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+
+        if self.read_idx == 0 {
+            self.towels = line
+                .split(", ")
+                .map(|s| Towel::from_str(s).unwrap())
+                .collect();
+        } else if self.read_idx > 1 {
+            self.cache.clear();
+            let arrangements = count_arrangements(line, &self.towels, &mut self.cache);
+            self.cache_peak.update(self.cache.len());
+            if arrangements > 0 {
+                self.designs_made += 1;
+            }
+            self.arrangements += arrangements;
+        }
+        self.read_idx += 1;
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.designs_made.into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        self.arrangements.into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "towels",
+            peak: self.towels.len(),
+            capacity: self.towels.capacity(),
+        });
+        report(Usage {
+            name: "cache",
+            peak: self.cache_peak.get(),
+            capacity: self.cache.capacity(),
+        });
+    }
+}
+
+/// Counts the ways to make `design`, the rest of the design whose counts are cached
+fn count_arrangements(design: &str, towels: &Towels, cache: &mut Cache) -> u64 {
+    if design.is_empty() {
+        return 1;
+    }
+
+    cache.memoize(design.len(), |cache| {
+        towels
+            .iter()
+            .filter_map(|towel| design.strip_prefix(towel.as_str()))
+            .map(|rest| count_arrangements(rest, towels, cache))
+            .sum()
+    })
+}
@@ -0,0 +1,56 @@
+#![no_std]
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::Vec;
+
+#[derive(Default)]
+pub struct Day2 {
+    safe: usize,
+    safe_dampened: usize,
+}
+
+impl Solution for Day2 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+        let numbers: Vec<i32, 64> = line.split(' ').map(|x| x.parse().unwrap()).collect();
+
+        match unsafe_at(&numbers) {
+            None => {
+                self.safe += 1;
+                self.safe_dampened += 1;
+            }
+            Some(ix) => {
+                for rem in ix.saturating_sub(1)..=ix.saturating_add(1) {
+                    // Remove x and try again:
+                    let mut retry = numbers.clone();
+                    retry.remove(rem);
+                    if unsafe_at(&retry).is_none() {
+                        self.safe_dampened += 1;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.safe.into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        self.safe_dampened.into()
+    }
+}
+
+fn unsafe_at(levels: &[i32]) -> Option<usize> {
+    let mut dir = 0;
+
+    for (i, window) in levels.windows(2).enumerate() {
+        match window[1] - window[0] {
+            x if (1..=3).contains(&x) && dir >= 0 => dir = 1,
+            x if (-3..0).contains(&x) && dir <= 0 => dir = -1,
+            _ => return Some(i),
+        };
+    }
+    None
+}
@@ -0,0 +1,209 @@
+#![no_std]
+use aoc_common::bits::{words, BitGrid};
+use aoc_common::grid;
+use aoc_common::memory::Usage;
+use aoc_common::params::{Param, Params};
+use aoc_common::path::{self, Search};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "cheat1",
+        default: 2,
+        min: 0,
+        max: 100,
+        help: "How many picoseconds a cheat lasts in part 1",
+    },
+    Param {
+        name: "cheat2",
+        default: 20,
+        min: 0,
+        max: 100,
+        help: "How many picoseconds a cheat lasts in part 2",
+    },
+    Param {
+        name: "saving",
+        default: 100,
+        min: 1,
+        max: u16::MAX as i64,
+        help: "How many picoseconds a cheat must save to count",
+    },
+];
+
+const MAP_SIZE: usize = 141;
+/// Which tiles are part of the track
+type Map = BitGrid<{ words(MAP_SIZE) }, MAP_SIZE>;
+type Pos = (i16, i16);
+/// The distance from the start to every tile of the track, in 16 bits each
+type Distances = Search<Pos, u16, { MAP_SIZE * MAP_SIZE }, 1024>;
+
+pub struct Day20 {
+    map: Map,
+    start: Pos,
+    end: Pos,
+    /// The distances part 1 measured, which both parts look up the cheats in
+    distances: Distances,
+    /// How many tiles of the track were measured
+    measured: usize,
+    cheat_lengths: [i16; 2],
+    min_saving: u16,
+}
+
+impl Default for Day20 {
+    fn default() -> Self {
+        Self {
+            map: Map::new(),
+            start: (0, 0),
+            end: (0, 0),
+            distances: Distances::new(),
+            measured: 0,
+            cheat_lengths: [2, 20],
+            min_saving: 100,
+        }
+    }
+}
+
+impl Solution for Day20 {
+    fn params() -> &'static [Param] {
+        PARAMS
+    }
+
+    fn configure(&mut self, params: &Params) {
+        self.cheat_lengths = [params.get("cheat1"), params.get("cheat2")];
+        self.min_saving = params.get("saving");
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        let y = self.map.height() as i16;
+        if let Some(x) = line.iter().position(|&b| b == b'S') {
+            self.start = (x as i16, y);
+        }
+        if let Some(x) = line.iter().position(|&b| b == b'E') {
+            self.end = (x as i16, y);
+        }
+        self.map
+            .push_row(line, |b| match b {
+                b'#' => false,
+                b'.' | b'S' | b'E' => true,
+                x => panic!("Unexpected byte on map: {x}"),
+            })
+            .unwrap();
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        match measure_track(&mut self.distances, &self.map, self.start, self.end) {
+            Ok(Some(base_distance)) => {
+                let _ = writeln!(out, "Base distance: {base_distance}");
+            }
+            Ok(None) => return Answer::Unsolved,
+            Err(e) => {
+                let _ = writeln!(out, "{e}");
+                return Answer::Unsolved;
+            }
+        }
+        self.measured = self
+            .map
+            .iter()
+            .filter(|track| {
+                let pos = (track.x as i16, track.y as i16);
+                self.distances.distance(index(&pos)).is_some()
+            })
+            .count();
+        self.count_cheats(self.cheat_lengths[0])
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        if self.distances.distance(index(&self.end)).is_none() {
+            return Answer::Unsolved;
+        }
+        self.count_cheats(self.cheat_lengths[1])
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "distances",
+            peak: self.measured,
+            capacity: MAP_SIZE * MAP_SIZE,
+        });
+    }
+}
+
+impl Day20 {
+    /// Counts the cheats of up to `length` that save enough time, from the distances
+    /// part 1 measured
+    fn count_cheats(&self, length: i16) -> Answer {
+        let (map, distances) = (&self.map, &self.distances);
+
+        let mut cheats = 0;
+        for track in map.iter() {
+            let pos = (track.x as i16, track.y as i16);
+            let Some(start_cost) = distances.distance(index(&pos)) else {
+                continue;
+            };
+            cheats += cheat_radius(map, pos, length)
+                .filter_map(|end| {
+                    let end_cost = distances.distance(index(&end))?;
+                    end_cost.checked_sub(start_cost + taxicab_dist(&pos, &end))
+                })
+                .filter(|&saving| saving >= self.min_saving)
+                .count();
+        }
+        cheats.into()
+    }
+}
+
+fn index((x, y): &Pos) -> usize {
+    *y as usize * MAP_SIZE + *x as usize
+}
+
+/// Fills in the distance to every tile of the track, and returns the distance to the end
+fn measure_track(
+    distances: &mut Distances,
+    map: &Map,
+    start: Pos,
+    end: Pos,
+) -> Result<Option<u16>, path::Error> {
+    // Explores the whole track, the cheats need the distance to every tile
+    distances.bfs(
+        &[start],
+        index,
+        |(x, y), f| {
+            for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if is_open(map, (nx, ny)) {
+                    f((nx, ny));
+                }
+            }
+        },
+        |_| false,
+    )?;
+
+    Ok(distances.distance(index(&end)))
+}
+
+fn is_open(map: &Map, (x, y): Pos) -> bool {
+    map.is_set(grid::Pos::new(x as isize, y as isize))
+}
+
+/// Returns all open tiles within a Taxicab circle centered on
+/// pos with the given radius.
+fn cheat_radius(map: &Map, pos: Pos, radius: i16) -> impl Iterator<Item = Pos> + '_ {
+    //   #
+    //  ###
+    // #####
+    //  ###
+    //   #
+    (-radius..=radius)
+        .flat_map(move |dy| {
+            let xrad = radius - dy.abs();
+            (-xrad..=xrad).map(move |dx| (pos.0 + dx, pos.1 + dy))
+        })
+        .filter(move |&end| end != pos && is_open(map, end))
+}
+
+fn taxicab_dist(start: &Pos, end: &Pos) -> u16 {
+    let dx = (end.0 - start.0).abs();
+    let dy = (end.1 - start.1).abs();
+    (dx + dy) as u16
+}
@@ -1,54 +1,33 @@
-#![deny(clippy::mem_forget)] // core::mem::forget is dangerous on ESP32
 #![no_std]
-#![no_main]
-use aoc_common::io;
-use esp_backtrace as _;
-use esp_hal::usb_serial_jtag::UsbSerialJtag;
-use esp_hal::{delay::Delay, prelude::*};
-use esp_println::println;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take};
 use nom::character::complete::{self};
 use nom::combinator::{iterator, map, value};
-use nom::multi::fold_many1;
 use nom::sequence::{delimited, preceded, separated_pair};
 use nom::IResult;
 
-#[entry]
-fn main() -> ! {
-    let peripherals = esp_hal::init(esp_hal::Config::default());
-
-    let delay = Delay::new();
-    let mut usb_serial = UsbSerialJtag::new(peripherals.USB_DEVICE);
-    let mut reader = io::LineReader::<4096>::new();
-    let mut eof: bool = false;
-
-    let mut machine = Machine::new();
-
-    while !eof {
-        delay.delay(1.millis());
-        reader.clear();
-
-        match reader.read_until_newline(&mut usb_serial) {
-            Ok(true) => {
-                let line = reader.line();
+#[derive(Default)]
+pub struct Day3 {
+    machine: Machine,
+}
 
-                let parser = alt((instr_do, instr_dont, instr_mul, garbage));
+impl Solution for Day3 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let parser = alt((instr_do, instr_dont, instr_mul, garbage));
 
-                for instr in &mut iterator(line, parser) {
-                    machine.exec(instr);
-                }
-            }
-            Ok(false) => eof = true,
-            Err(e) => println!("Error reading! {}", e),
+        for instr in &mut iterator(line, parser) {
+            self.machine.exec(instr);
         }
     }
 
-    println!("Sum: {}", machine.sum);
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        (self.machine.sum_all as u64).into()
+    }
 
-    println!("<EOT>");
-    loop {
-        delay.delay(100.millis());
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        (self.machine.sum as u64).into()
     }
 }
 
@@ -83,23 +62,33 @@ fn garbage(inp: &[u8]) -> IResult<&[u8], Instr> {
 #[derive(Copy, Clone)]
 struct Machine {
     mul_enabled: bool,
+    /// Sum of all multiplications, ignoring do() and don't()
+    sum_all: i32,
     sum: i32,
 }
 
-impl Machine {
-    fn new() -> Self {
+impl Default for Machine {
+    fn default() -> Self {
         Machine {
             mul_enabled: true,
+            sum_all: 0,
             sum: 0,
         }
     }
+}
 
+impl Machine {
     fn exec(&mut self, instr: Instr) {
         match instr {
-            Instr::Mul(a, b) if self.mul_enabled => self.sum += a * b,
+            Instr::Mul(a, b) => {
+                self.sum_all += a * b;
+                if self.mul_enabled {
+                    self.sum += a * b;
+                }
+            }
             Instr::Do => self.mul_enabled = true,
             Instr::Dont => self.mul_enabled = false,
-            _ => (),
+            Instr::Noop => (),
         }
     }
 }
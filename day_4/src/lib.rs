@@ -1,11 +1,6 @@
-#![deny(clippy::mem_forget)] // core::mem::forget is dangerous on ESP32
 #![no_std]
-#![no_main]
-use aoc_common::io;
-use esp_backtrace as _;
-use esp_hal::usb_serial_jtag::UsbSerialJtag;
-use esp_hal::{delay::Delay, prelude::*};
-use esp_println::println;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
 use heapless::{Deque, Vec};
 
 // We only need 7 lines in memory at once to search for XMAS in all directions.
@@ -13,83 +8,75 @@ use heapless::{Deque, Vec};
 // `Option`s are used to signify the boundaries of the puzzle.
 type BufView = Deque<Option<Vec<u8, 140>>, 7>;
 
-#[entry]
-fn main() -> ! {
-    let peripherals = esp_hal::init(esp_hal::Config::default());
+pub struct Day4 {
+    buf: BufView,
+    xmas_count: usize,
+    x_mas_count: usize,
+    flushed: bool,
+}
 
-    let delay = Delay::new();
-    let mut usb_serial = UsbSerialJtag::new(peripherals.USB_DEVICE);
-    let mut reader = io::LineReader::<140>::new();
-    let mut eof: bool = false;
+impl Default for Day4 {
+    fn default() -> Self {
+        let mut buf: BufView = Deque::new();
 
-    // We only need 7 lines in memory at once to search for XMAS in all directions.
-    // The "middle" element is where we're currently looking for XMAS'es.
-    // `Option`s are used to signify the boundaries of the puzzle.
-    let mut buf: BufView = Deque::new();
+        // Prime the buffer so we always have a full view:
+        for _ in 0..buf.capacity() {
+            buf.push_back(None).unwrap();
+        }
 
-    // Prime the buffer so we always have a full view:
-    for _ in 0..buf.capacity() {
-        buf.push_back(None).unwrap();
+        Self {
+            buf,
+            xmas_count: 0,
+            x_mas_count: 0,
+            flushed: false,
+        }
     }
+}
 
-    let mut xmas_count: usize = 0;
-    let mut x_mas_count: usize = 0;
-
-    while !eof {
-        delay.delay(1.millis());
-        reader.clear();
-
-        match reader.read_until_newline(&mut usb_serial) {
-            Ok(true) => {
-                let line = reader.line();
-
-                // Shift one line into the buf
-                buf.pop_front();
-                buf.push_back(Some(Vec::from_slice(line).unwrap())).unwrap();
-
-                // If we have text in our search line, we can start counting:
-                if let Some(_) = buf.iter().nth(3).unwrap() {
-                    let (p1_count, p2_count) = count_xmas_on_line(&buf);
-                    xmas_count += p1_count;
-                    x_mas_count += p2_count;
-                }
-            }
-            Ok(false) => eof = true,
-            Err(e) => println!("Error reading! {}", e),
+impl Solution for Day4 {
+    fn parse_line(&mut self, line: &[u8]) {
+        // Shift one line into the buf
+        self.buf.pop_front();
+        self.buf
+            .push_back(Some(Vec::from_slice(line).unwrap()))
+            .unwrap();
+
+        // If we have text in our search line, we can start counting:
+        if self.buf.iter().nth(3).unwrap().is_some() {
+            self.count();
         }
-
-        // print_buf(&buf);
-        // println!("---")
     }
 
-    // When all lines are read, we still have to continue our push loop until
-    // there's no more lines in focus:
-    for _ in 0..3 {
-        buf.pop_front();
-        buf.push_back(None).unwrap();
-        let (p1_count, p2_count) = count_xmas_on_line(&buf);
-        xmas_count += p1_count;
-        x_mas_count += p2_count;
-        // print_buf(&buf);
-        // println!("---")
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.flush();
+        self.xmas_count.into()
     }
 
-    println!("XMAS: {}", xmas_count);
-    println!("X-MAS: {}", x_mas_count);
-
-    println!("<EOT>");
-    loop {
-        delay.delay(100.millis());
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        self.flush();
+        self.x_mas_count.into()
     }
 }
 
-fn print_buf(buf: &BufView) {
-    for line in buf.iter() {
-        let content = match line {
-            None => "",
-            Some(l) => core::str::from_utf8(&l).unwrap(),
-        };
-        println!("{content}");
+impl Day4 {
+    fn count(&mut self) {
+        let (p1_count, p2_count) = count_xmas_on_line(&self.buf);
+        self.xmas_count += p1_count;
+        self.x_mas_count += p2_count;
+    }
+
+    /// When all lines are read, we still have to continue our push loop until
+    /// there's no more lines in focus.
+    fn flush(&mut self) {
+        if self.flushed {
+            return;
+        }
+        for _ in 0..3 {
+            self.buf.pop_front();
+            self.buf.push_back(None).unwrap();
+            self.count();
+        }
+        self.flushed = true;
     }
 }
 
@@ -106,12 +93,12 @@ fn count_xmas_on_line(buf: &BufView) -> (usize, usize) {
         .expect("Middle of bufview is empty!");
 
     // for XMAS We always start our search on an X on our focused line:
-    for (x, _) in middle.iter().enumerate().filter(|(_, c)| **c == 'X' as u8) {
+    for (x, _) in middle.iter().enumerate().filter(|(_, c)| **c == b'X') {
         xmas_count += star_count_xmas(buf, x);
     }
 
     // for X-MAS We always start our search on an A on our focused line:
-    for (x, _) in middle.iter().enumerate().filter(|(_, c)| **c == 'A' as u8) {
+    for (x, _) in middle.iter().enumerate().filter(|(_, c)| **c == b'A') {
         x_mas_count += star_count_x_mas(buf, x);
     }
 
@@ -121,9 +108,7 @@ fn count_xmas_on_line(buf: &BufView) -> (usize, usize) {
 // Part 1
 // During vertical and diagonal search, which letter do we expect in which y coordinate.
 // During horizontal search, which letter do we expect in which x offset.
-const EXP_CHAR: [u8; 7] = [
-    'S' as u8, 'A' as u8, 'M' as u8, 'X' as u8, 'M' as u8, 'A' as u8, 'S' as u8,
-];
+const EXP_CHAR: [u8; 7] = *b"SAMXMAS";
 
 /// Given the x coordinate of an 'X', count the amounts of "MAS" originating from there.
 fn star_count_xmas(buf: &BufView, at_idx: usize) -> usize {
@@ -134,13 +119,12 @@ fn star_count_xmas(buf: &BufView, at_idx: usize) -> usize {
     for (y_idx, line) in buf.iter().enumerate() {
         let dir_offset = if y_idx < 3 { 0 } else { 5 };
 
-        if let None = line {
-            candidates[0 + dir_offset] = 0;
+        let Some(text) = line else {
+            candidates[dir_offset] = 0;
             candidates[1 + dir_offset] = 0;
             candidates[2 + dir_offset] = 0;
             continue;
-        }
-        let text = line.as_ref().unwrap();
+        };
 
         if y_idx == 3 {
             // On the third line, we search left and right.
@@ -175,19 +159,20 @@ fn star_count_xmas(buf: &BufView, at_idx: usize) -> usize {
 // Part 2
 /// Given the x coordinate of an 'A', count the amounts of "MAS" centered there.
 fn star_count_x_mas(buf: &BufView, at_idx: usize) -> usize {
-    let top_line = buf.iter().nth(2).unwrap();
-    let bot_line = buf.iter().nth(4).unwrap();
-
-    if top_line.is_none() || bot_line.is_none() {
+    let (Some(top_txt), Some(bot_txt)) = (buf.iter().nth(2).unwrap(), buf.iter().nth(4).unwrap())
+    else {
         return 0;
-    }
-
-    let top_txt = top_line.as_ref().unwrap();
-    let bot_txt = bot_line.as_ref().unwrap();
+    };
 
-    let c1 = top_txt.get(at_idx - 1).unwrap_or(&b'.');
+    let c1 = at_idx
+        .checked_sub(1)
+        .and_then(|x| top_txt.get(x))
+        .unwrap_or(&b'.');
     let c2 = top_txt.get(at_idx + 1).unwrap_or(&b'.');
-    let c3 = bot_txt.get(at_idx - 1).unwrap_or(&b'.');
+    let c3 = at_idx
+        .checked_sub(1)
+        .and_then(|x| bot_txt.get(x))
+        .unwrap_or(&b'.');
     let c4 = bot_txt.get(at_idx + 1).unwrap_or(&b'.');
 
     match (c1, c2, c3, c4) {
@@ -0,0 +1,115 @@
+#![no_std]
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::{Entry, FnvIndexMap, FnvIndexSet, Vec};
+
+type RuleSet = FnvIndexMap<u8, FnvIndexSet<u8, 64>, 128>;
+type Update = Vec<u8, 64>;
+
+pub struct Day5 {
+    reading_rules: bool,
+    /// PageNr => Pages that need to come AFTER it.
+    rules: RuleSet,
+    ok_sum: usize,
+    reordered_sum: usize,
+}
+
+impl Default for Day5 {
+    fn default() -> Self {
+        Self {
+            reading_rules: true,
+            rules: FnvIndexMap::new(),
+            ok_sum: 0,
+            reordered_sum: 0,
+        }
+    }
+}
+
+impl Solution for Day5 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+
+        if line.is_empty() {
+            self.reading_rules = false;
+        } else if self.reading_rules {
+            let mut parts = line.split('|').map(|s| s.parse::<u8>().unwrap());
+            match self.rules.entry(parts.next().unwrap()) {
+                Entry::Vacant(v) => {
+                    let mut set = FnvIndexSet::new();
+                    set.insert(parts.next().unwrap()).unwrap();
+                    v.insert(set).unwrap();
+                }
+                Entry::Occupied(mut v) => {
+                    v.get_mut().insert(parts.next().unwrap()).unwrap();
+                }
+            }
+        } else {
+            let mut update: Update = line.split(',').map(|s| s.parse().unwrap()).collect();
+
+            match unsorted_at(&update, &self.rules) {
+                None => self.ok_sum += update_val(&update),
+                Some(page) => {
+                    reorder_update(&mut update, &self.rules, page);
+                    self.reordered_sum += update_val(&update);
+                }
+            }
+        }
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.ok_sum.into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        self.reordered_sum.into()
+    }
+}
+
+/// Returns the page which is not sorted correctly, or None if everything is sorted correctly.
+fn unsorted_at(update: &Update, rules: &RuleSet) -> Option<u8> {
+    let mut seen: FnvIndexSet<u8, 64> = FnvIndexSet::new();
+
+    for page in update {
+        let is_ok = match rules.get(page) {
+            Some(after) => seen.is_disjoint(after),
+            None => true,
+        };
+
+        if !is_ok {
+            return Some(*page);
+        }
+
+        seen.insert(*page).unwrap();
+    }
+
+    None
+}
+
+fn update_val(update: &Update) -> usize {
+    let middle_idx = update.len() / 2;
+    update[middle_idx] as usize
+}
+
+fn reorder_update(update: &mut Update, rules: &RuleSet, mut wrong_page: u8) {
+    loop {
+        let before = rules.get(&wrong_page).unwrap();
+        let wrong_idx = update.iter().position(|p| *p == wrong_page).unwrap();
+
+        // Get earliest index to move before
+        let earliest = before
+            .iter()
+            .filter_map(|pg| update.iter().position(|p| p == pg))
+            .min()
+            .unwrap();
+
+        // Move the wrong page to before the earliest it should be.
+        update.remove(wrong_idx);
+        update.insert(earliest, wrong_page).unwrap();
+
+        // Check again, if we good, we good
+        match unsorted_at(update, rules) {
+            Some(x) => wrong_page = x,
+            None => break,
+        };
+    }
+}
@@ -0,0 +1,132 @@
+#![no_std]
+use aoc_common::bits::{words, BitGrid};
+use aoc_common::geom::Dir4 as Dir;
+use aoc_common::grid::Pos as Coord;
+use aoc_common::memory::Usage;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::FnvIndexSet;
+
+const MAP_SIZE: usize = 130;
+/// Whether each tile is an obstacle
+type Map = BitGrid<{ words(MAP_SIZE) }, MAP_SIZE>;
+/// The tiles the guard visited
+type Path = BitGrid<{ words(MAP_SIZE) }, MAP_SIZE>;
+
+pub struct Day6 {
+    map: Map,
+    guard_pos: Coord,
+    guard_dir: Dir,
+    path: Path,
+}
+
+impl Default for Day6 {
+    fn default() -> Self {
+        Self {
+            map: Map::new(),
+            guard_pos: Coord::default(),
+            guard_dir: Dir::N,
+            path: Path::new(),
+        }
+    }
+}
+
+impl Solution for Day6 {
+    fn parse_line(&mut self, line: &[u8]) {
+        if let Some(x) = line.iter().position(|c| b"<^>v".contains(c)) {
+            self.guard_pos = Coord::new(x as isize, self.map.height() as isize);
+            self.guard_dir = Dir::from_u8(line[x]).unwrap();
+        }
+        self.map
+            .push_row(line, |c| match c {
+                b'#' => true,
+                b'.' | b'<' | b'^' | b'>' | b'v' => false,
+                x => panic!("Unsupported tile {x}"),
+            })
+            .unwrap();
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.path = Path::with_size(self.map.width(), self.map.height());
+        let _ = simulate(
+            &self.map,
+            &self.guard_pos,
+            &self.guard_dir,
+            Some(&mut self.path),
+        );
+        self.path.len().into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        find_looping_blockades(&mut self.map, &self.path, &self.guard_pos, &self.guard_dir).into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "path",
+            peak: self.path.len(),
+            capacity: MAP_SIZE * MAP_SIZE,
+        });
+        report(Usage {
+            name: "map",
+            peak: self.map.height(),
+            capacity: MAP_SIZE,
+        });
+    }
+}
+
+fn simulate(
+    map: &Map,
+    start_pos: &Coord,
+    start_dir: &Dir,
+    mut tracepath: Option<&mut Path>,
+) -> bool {
+    let mut guard_pos = *start_pos;
+    let mut guard_dir = *start_dir;
+    let mut path: FnvIndexSet<(Coord, Dir), 256> = FnvIndexSet::new();
+    let mut is_loop = false;
+
+    loop {
+        if let Some(tracepath) = tracepath.as_deref_mut() {
+            tracepath.insert(guard_pos);
+        }
+
+        guard_pos = guard_pos.step(guard_dir);
+
+        if !map.contains(guard_pos) {
+            break;
+        }
+
+        if map.is_set(guard_pos.step(guard_dir)) {
+            let is_new = path.insert((guard_pos, guard_dir)).unwrap();
+            if !is_new {
+                is_loop = true;
+                break;
+            }
+            guard_dir = guard_dir.turn_right();
+        }
+    }
+
+    is_loop
+}
+
+fn find_looping_blockades(
+    map: &mut Map,
+    orig_path: &Path,
+    start_pos: &Coord,
+    start_dir: &Dir,
+) -> usize {
+    let blockade_options = orig_path.iter().filter(|c| c != start_pos);
+    let mut hits: usize = 0;
+
+    for pos in blockade_options {
+        map.insert(pos);
+        let is_loop = simulate(map, start_pos, start_dir, None);
+        if is_loop {
+            hits += 1;
+        }
+        map.remove(pos);
+    }
+
+    hits
+}
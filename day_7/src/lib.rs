@@ -0,0 +1,80 @@
+#![no_std]
+use aoc_common::num;
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::Write;
+use heapless::Vec;
+
+type Terms = Vec<u64, 16>;
+
+#[derive(Default)]
+pub struct Day7 {
+    sum: u64,
+    sum_with_concat: u64,
+}
+
+impl Solution for Day7 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = core::str::from_utf8(line).unwrap();
+
+        let (test_val_str, rest) = line.split_once(": ").unwrap();
+        let test_val: u64 = test_val_str.parse().unwrap();
+        let terms: Terms = rest.split(' ').map(|s| s.parse().unwrap()).collect();
+
+        if is_solvable(test_val, terms[0], &terms[1..], false) {
+            self.sum += test_val;
+        }
+        if is_solvable(test_val, terms[0], &terms[1..], true) {
+            self.sum_with_concat += test_val;
+        }
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        self.sum.into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        self.sum_with_concat.into()
+    }
+}
+
+fn is_solvable(test_val: u64, acc: u64, terms: &[u64], with_concat: bool) -> bool {
+    let next = terms[0];
+    let rest = &terms[1..];
+    let leaf = rest.is_empty();
+
+    match acc.checked_add(next) {
+        Some(x) if leaf && x == test_val => return true,
+        Some(new_acc)
+            if !leaf
+                && new_acc <= test_val
+                && is_solvable(test_val, new_acc, rest, with_concat) =>
+        {
+            return true
+        }
+        _ => (),
+    }
+
+    match acc.checked_mul(next) {
+        Some(x) if leaf && x == test_val => return true,
+        Some(new_acc)
+            if !leaf
+                && new_acc <= test_val
+                && is_solvable(test_val, new_acc, rest, with_concat) =>
+        {
+            return true
+        }
+        _ => (),
+    }
+
+    if !with_concat {
+        return false;
+    }
+
+    match num::concat(acc, next) {
+        Some(x) if leaf && x == test_val => true,
+        Some(new_acc) if !leaf && new_acc <= test_val => {
+            is_solvable(test_val, new_acc, rest, with_concat)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,111 @@
+#![no_std]
+use aoc_common::iter;
+use aoc_common::memory::Usage;
+use aoc_common::solution::{Answer, Solution};
+use core::cmp::max;
+use core::fmt::Write;
+use heapless::{Entry, FnvIndexMap, FnvIndexSet, Vec};
+
+type Coord = (isize, isize);
+/// The most antennas of a single frequency
+const ANTENNAS: usize = 16;
+type Map = FnvIndexMap<u8, Vec<Coord, ANTENNAS>, 64>;
+
+#[derive(Default)]
+pub struct Day8 {
+    map: Map,
+    size: Coord,
+}
+
+impl Solution for Day8 {
+    fn parse_line(&mut self, line: &[u8]) {
+        let y = self.size.1;
+
+        for (x, byte) in line.iter().enumerate() {
+            if *byte != b'.' {
+                match self.map.entry(*byte) {
+                    Entry::Vacant(ent) => {
+                        let mut items = Vec::new();
+                        items.push((x as isize, y)).unwrap();
+                        ent.insert(items).unwrap();
+                    }
+                    Entry::Occupied(mut ent) => ent.get_mut().push((x as isize, y)).unwrap(),
+                }
+            }
+
+            self.size.0 = max(self.size.0, x as isize + 1);
+        }
+
+        self.size.1 += 1;
+    }
+
+    fn part1(&mut self, _out: &mut dyn Write) -> Answer {
+        count_antinodes(&self.map, &self.size).into()
+    }
+
+    fn part2(&mut self, _out: &mut dyn Write) -> Answer {
+        count_antinodes_with_harmonics(&self.map, &self.size).into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "frequencies",
+            peak: self.map.len(),
+            capacity: self.map.capacity(),
+        });
+        report(Usage {
+            name: "antennas",
+            peak: self
+                .map
+                .values()
+                .map(|pts| pts.len())
+                .max()
+                .unwrap_or_default(),
+            capacity: ANTENNAS,
+        });
+    }
+}
+
+fn count_antinodes(map: &Map, size: &Coord) -> usize {
+    let mut antis: FnvIndexSet<Coord, 512> = FnvIndexSet::new();
+
+    for (_freq, pts) in map.iter() {
+        for (pt1, pt2) in iter::PairIterator::new(pts) {
+            for (start, through) in [(pt1, pt2), (pt2, pt1)] {
+                let (x, y) = project(start, through);
+                if (0..size.0).contains(&x) && (0..size.1).contains(&y) {
+                    antis.insert((x, y)).unwrap();
+                }
+            }
+        }
+    }
+
+    antis.len()
+}
+
+fn count_antinodes_with_harmonics(map: &Map, size: &Coord) -> usize {
+    let mut antis: FnvIndexSet<Coord, 2048> = FnvIndexSet::new();
+
+    for (_freq, pts) in map.iter() {
+        for (pt1, pt2) in iter::PairIterator::new(pts) {
+            for (start, through) in [(pt1, pt2), (pt2, pt1)] {
+                let mut proj_pt = *start;
+                let mut through_pt = *through;
+                while (0..size.0).contains(&proj_pt.0) && (0..size.1).contains(&proj_pt.1) {
+                    antis.insert(proj_pt).unwrap();
+                    let next_proj = through_pt;
+                    through_pt = project(&proj_pt, &through_pt);
+                    proj_pt = next_proj;
+                }
+            }
+        }
+    }
+
+    antis.len()
+}
+
+fn project(pt: &Coord, through: &Coord) -> Coord {
+    let dx = through.0 - pt.0;
+    let dy = through.1 - pt.1;
+    (through.0 + dx, through.1 + dy)
+}
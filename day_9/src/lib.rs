@@ -0,0 +1,315 @@
+#![no_std]
+use aoc_common::memory::{HighWater, Usage};
+use aoc_common::solution::{Answer, Solution};
+use core::fmt::{self, Display, Write};
+use heapless::Vec;
+
+/// The disk map: the sizes of the files and the free spans between them, alternating.
+/// File `id` is at index `2 * id`.
+type DiskMap = Vec<u8, DISK_LENGTH>;
+const DISK_LENGTH: usize = 20_000;
+/// The most free spans a disk map has, one after every file but the last
+const MAX_SPANS: usize = DISK_LENGTH / 2;
+/// The largest a file or free span can be
+const MAX_SIZE: usize = 9;
+
+/// Why the disk map couldn't be read
+#[derive(Debug, Clone, Copy)]
+enum ParseError {
+    /// The entry at this index isn't a digit
+    NotADigit { idx: usize },
+    /// More entries than [`DISK_LENGTH`]
+    TooLong,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NotADigit { idx } => write!(f, "Entry {idx} isn't a digit"),
+            ParseError::TooLong => write!(f, "The disk map is longer than {DISK_LENGTH} entries"),
+        }
+    }
+}
+
+/// The disk map is a single, very long line. It may be handed to us in chunks, so it's
+/// collected across calls to `parse_line`.
+#[derive(Default)]
+pub struct Day9 {
+    /// Why the disk map couldn't be read, if it couldn't
+    error: Option<ParseError>,
+    disk: DiskMap,
+    /// The free spans that were indexed
+    spans_peak: HighWater,
+}
+
+impl Solution for Day9 {
+    fn chunked() -> bool {
+        true
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.parse(line) {
+            self.error = Some(e);
+        }
+    }
+
+    fn part1(&mut self, out: &mut dyn Write) -> Answer {
+        if let Some(e) = self.error {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+        compact(&self.disk).into()
+    }
+
+    fn part2(&mut self, out: &mut dyn Write) -> Answer {
+        if let Some(e) = self.error {
+            let _ = writeln!(out, "{e}");
+            return Answer::Unsolved;
+        }
+        defrag(&self.disk, &mut self.spans_peak).into()
+    }
+
+    fn usage(&self, report: &mut dyn FnMut(Usage)) {
+        report(Usage {
+            name: "disk",
+            peak: self.disk.len(),
+            capacity: self.disk.capacity(),
+        });
+        report(Usage {
+            name: "spans",
+            peak: self.spans_peak.get(),
+            capacity: MAX_SPANS,
+        });
+    }
+}
+
+impl Day9 {
+    /// Adds a chunk of the disk map
+    fn parse(&mut self, line: &[u8]) -> Result<(), ParseError> {
+        for byte in line {
+            let idx = self.disk.len();
+            let size = (*byte as char)
+                .to_digit(10)
+                .ok_or(ParseError::NotADigit { idx })?;
+            self.disk
+                .push(size as u8)
+                .map_err(|_| ParseError::TooLong)?;
+        }
+        Ok(())
+    }
+}
+
+/// The file at an index of the disk map, None for free space
+fn file_id(idx: usize) -> Option<u64> {
+    idx.is_multiple_of(2).then_some(idx as u64 / 2)
+}
+
+/// Moves the file blocks from the end of the disk into the free space at the front,
+/// one block at a time. Works from both ends of the disk map at once, without moving
+/// anything, and returns the checksum of where the blocks end up.
+fn compact(disk: &DiskMap) -> u64 {
+    let mut sum: u64 = 0;
+    let mut pos: u64 = 0;
+    // Entries front..back are still where they were
+    let (mut front, mut back) = (0, disk.len());
+    // The file that's being moved forward, and how many of its blocks are left
+    let (mut moving, mut left) = (0, 0);
+
+    while front < back {
+        let size = disk[front];
+        let Some(id) = file_id(front) else {
+            // Free space, so fill up from the back
+            let mut free = size;
+            while free > 0 {
+                while left == 0 && front + 1 < back {
+                    back -= 1;
+                    if let Some(id) = file_id(back) {
+                        (moving, left) = (id, disk[back]);
+                    }
+                }
+                if left == 0 {
+                    break;
+                }
+                let size = free.min(left);
+                sum += checksum(moving, pos, size);
+                pos += size as u64;
+                free -= size;
+                left -= size;
+            }
+            front += 1;
+            continue;
+        };
+        sum += checksum(id, pos, size);
+        pos += size as u64;
+        front += 1;
+    }
+
+    // What's left of the last file stays put, right after everything that moved
+    sum + checksum(moving, pos, left)
+}
+
+/// Moves every file once, from the last one to the first, into the leftmost free span
+/// that fits it, if there's one before the file. Returns the checksum of where the files
+/// end up. The leftmost span that fits is the leftmost of the first spans of the file's
+/// size and up.
+fn defrag(disk: &DiskMap, spans_peak: &mut HighWater) -> u64 {
+    let mut spans = Spans::new();
+    let mut pos: u32 = 0;
+    for (idx, &size) in disk.iter().enumerate() {
+        if file_id(idx).is_none() && size > 0 {
+            spans.add(size, pos);
+        }
+        pos += size as u32;
+    }
+    spans_peak.update(spans.len());
+
+    let mut sum: u64 = 0;
+    for (idx, &size) in disk.iter().enumerate().rev() {
+        pos -= size as u32;
+        // Empty files add nothing to the checksum wherever they go
+        let Some(id) = file_id(idx).filter(|_| size > 0) else {
+            continue;
+        };
+
+        let leftmost = (size..=MAX_SIZE as u8)
+            .filter_map(|span_size| Some((spans.first(span_size)?, span_size)))
+            .min()
+            .filter(|&(span, _)| spans.start(span) < pos);
+        let to = match leftmost {
+            Some((span, span_size)) => spans.fill(span, span_size, size),
+            None => pos,
+        };
+        sum += checksum(id, to as u64, size);
+    }
+    sum
+}
+
+/// The free spans, numbered from the left, in a min-heap per size. A span only ever
+/// shrinks, so the spans stay in the order of their numbers, and the first span of a
+/// heap is the leftmost one of that size.
+///
+/// Every span is in one heap at most, so the heaps share their nodes: they're pairing
+/// heaps, which link a span to its first child and its next sibling by number. They
+/// never need more nodes than the disk map has spans, however the spans are sized.
+struct Spans {
+    start: Vec<u32, MAX_SPANS>,
+    child: Vec<u16, MAX_SPANS>,
+    sibling: Vec<u16, MAX_SPANS>,
+    /// The heap of spans of each size from 1
+    roots: [u16; MAX_SIZE],
+}
+
+/// No span
+const NIL: u16 = u16::MAX;
+
+impl Spans {
+    fn new() -> Self {
+        Self {
+            start: Vec::new(),
+            child: Vec::new(),
+            sibling: Vec::new(),
+            roots: [NIL; MAX_SIZE],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.start.len()
+    }
+
+    /// Adds a span right of every span that was added before. There's room for every
+    /// free span of a disk map that fits.
+    fn add(&mut self, size: u8, start: u32) {
+        let span = self.len() as u16;
+        self.start.push(start).unwrap();
+        self.child.push(NIL).unwrap();
+        self.sibling.push(NIL).unwrap();
+        self.push(size, span);
+    }
+
+    fn start(&self, span: u16) -> u32 {
+        self.start[span as usize]
+    }
+
+    /// The leftmost span of a size
+    fn first(&self, size: u8) -> Option<u16> {
+        let root = self.roots[size as usize - 1];
+        (root != NIL).then_some(root)
+    }
+
+    /// Moves a file of `size` into the leftmost span of `span_size`, which is `span`.
+    /// What the file doesn't fill stays free, as a smaller span. Returns where the file
+    /// goes.
+    fn fill(&mut self, span: u16, span_size: u8, size: u8) -> u32 {
+        let children = self.child[span as usize];
+        self.roots[span_size as usize - 1] = self.merge_pairs(children);
+        let to = self.start(span);
+        let rest = span_size - size;
+        if rest > 0 {
+            self.start[span as usize] += size as u32;
+            self.child[span as usize] = NIL;
+            self.push(rest, span);
+        }
+        to
+    }
+
+    fn push(&mut self, size: u8, span: u16) {
+        let root = self.roots[size as usize - 1];
+        self.roots[size as usize - 1] = self.meld(root, span);
+    }
+
+    /// Joins two heaps, making the root that's further right the first child of the other
+    fn meld(&mut self, a: u16, b: u16) -> u16 {
+        // NIL is right of every span
+        if a == NIL || b == NIL {
+            return a.min(b);
+        }
+        let (root, other) = (a.min(b), a.max(b));
+        self.sibling[other as usize] = self.child[root as usize];
+        self.child[root as usize] = other;
+        root
+    }
+
+    /// Joins the heaps in a list of siblings into one: first in pairs from the left, then
+    /// the pairs from the right
+    fn merge_pairs(&mut self, first: u16) -> u16 {
+        // The pairs, linked through their siblings, the rightmost first
+        let mut pairs = NIL;
+        let mut next = first;
+        while next != NIL {
+            let a = next;
+            let b = self.sibling[a as usize];
+            self.sibling[a as usize] = NIL;
+            let pair = match b {
+                NIL => {
+                    next = NIL;
+                    a
+                }
+                _ => {
+                    next = self.sibling[b as usize];
+                    self.sibling[b as usize] = NIL;
+                    self.meld(a, b)
+                }
+            };
+            self.sibling[pair as usize] = pairs;
+            pairs = pair;
+        }
+
+        let mut root = NIL;
+        while pairs != NIL {
+            let pair = pairs;
+            pairs = self.sibling[pair as usize];
+            self.sibling[pair as usize] = NIL;
+            root = self.meld(root, pair);
+        }
+        root
+    }
+}
+
+/// The checksum of file `id` taking up `size` blocks from `pos`
+fn checksum(id: u64, pos: u64, size: u8) -> u64 {
+    let size = size as u64;
+    id * (pos * size + size * size.saturating_sub(1) / 2)
+}
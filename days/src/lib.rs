@@ -0,0 +1,26 @@
+#![no_std]
+use aoc_common::registry::Day;
+
+/// Every day that has a solution, in order
+pub const DAYS: &[Day] = &[
+    Day::new::<aoc_2024_day_1::Day1>(1, "Historian Hysteria"),
+    Day::new::<aoc_2024_day_2::Day2>(2, "Red-Nosed Reports"),
+    Day::new::<aoc_2024_day_3::Day3>(3, "Mull It Over"),
+    Day::new::<aoc_2024_day_4::Day4>(4, "Ceres Search"),
+    Day::new::<aoc_2024_day_5::Day5>(5, "Print Queue"),
+    Day::new::<aoc_2024_day_6::Day6>(6, "Guard Gallivant"),
+    Day::new::<aoc_2024_day_7::Day7>(7, "Bridge Repair"),
+    Day::new::<aoc_2024_day_8::Day8>(8, "Resonant Collinearity"),
+    Day::new::<aoc_2024_day_9::Day9>(9, "Disk Fragmenter"),
+    Day::new::<aoc_2024_day_10::Day10>(10, "Hoof It"),
+    Day::new::<aoc_2024_day_11::Day11>(11, "Plutonian Pebbles"),
+    Day::new::<aoc_2024_day_12::Day12>(12, "Garden Groups"),
+    Day::new::<aoc_2024_day_13::Day13>(13, "Claw Contraption"),
+    Day::new::<aoc_2024_day_14::Day14>(14, "Restroom Redoubt"),
+    Day::new::<aoc_2024_day_15::Day15>(15, "Warehouse Woes"),
+    Day::new::<aoc_2024_day_16::Day16>(16, "Reindeer Maze"),
+    Day::new::<aoc_2024_day_17::Day17>(17, "Chronospatial Computer"),
+    Day::new::<aoc_2024_day_18::Day18>(18, "RAM Run"),
+    Day::new::<aoc_2024_day_19::Day19>(19, "Linen Layout"),
+    Day::new::<aoc_2024_day_20::Day20>(20, "Race Condition"),
+];
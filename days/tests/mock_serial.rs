@@ -0,0 +1,100 @@
+use aoc_2024_days::DAYS;
+use aoc_common::frame::{self, Framed, Kind};
+use aoc_common::io::LineReader;
+use aoc_common::mock::{LineEnding, MockSerial};
+use aoc_common::output::Record;
+use aoc_common::runner::{self, Meters, LINE_LENGTH};
+use aoc_common::solution::Answer;
+use embedded_io::Read;
+
+/// The day's title line, followed by a record per answer
+fn expected(day: &str, answers: &[(u8, u64)]) -> String {
+    let number = day.split(' ').nth(1).unwrap().trim_end_matches(':');
+    let mut res = format!("{day}\n");
+    for &(part, value) in answers {
+        let value = Answer::Number(value);
+        let record = Record::new(number.parse().unwrap(), part, &value);
+        res += &format!("{record}\n");
+    }
+    res
+}
+
+/// Runs an input and returns the output, without the days' usage records
+fn run(reader: &mut impl Read) -> String {
+    let mut lines = LineReader::<LINE_LENGTH>::new();
+    let mut out = String::new();
+    assert!(runner::run(DAYS, reader, &mut lines, &mut out, Meters::default()).unwrap());
+    out.lines()
+        .filter(|line| !line.contains("\"usage\":"))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+#[test]
+fn test_day_over_mock_serial() {
+    let input = std::fs::read("../day_1/inputs/test.txt").unwrap();
+    let input = [&b"DAY 1\n"[..], input.as_slice()].concat();
+    let mut serial = Framed::new(MockSerial::new().upload(&input));
+
+    let out = run(&mut serial);
+    assert_eq!(
+        out,
+        expected("Day 1: Historian Hysteria", &[(1, 11), (2, 31)])
+    );
+    assert!(serial.inner().is_drained());
+}
+
+#[test]
+fn test_back_to_back_runs_with_unreliable_transport() {
+    let input = std::fs::read("../day_2/inputs/test.txt").unwrap();
+    let serial = MockSerial::new()
+        .line_ending(LineEnding::CrLf)
+        .max_read(5)
+        .upload(&[&b"DAY 2 PART 2\n"[..], input.as_slice()].concat())
+        .would_block()
+        .upload(&[&b"DAY 2 PART 1\n"[..], input.as_slice()].concat());
+    let mut serial = Framed::new(serial);
+
+    assert_eq!(
+        run(&mut serial),
+        expected("Day 2: Red-Nosed Reports", &[(2, 4)])
+    );
+    assert_eq!(
+        run(&mut serial),
+        expected("Day 2: Red-Nosed Reports", &[(1, 2)])
+    );
+    assert!(serial.inner().is_drained());
+}
+
+#[test]
+fn test_long_line_arrives_intact() {
+    // Longer than a line and spread over many frames
+    let disk_map: Vec<u8> = (0..5001).map(|i| b'1' + (i * 7 % 9) as u8).collect();
+    let input = [&b"DAY 9\n"[..], disk_map.as_slice(), b"\n"].concat();
+
+    let expected = run(&mut input.as_slice());
+    let mut serial = Framed::new(MockSerial::new().max_read(7).upload(&input));
+    assert_eq!(run(&mut serial), expected);
+}
+
+#[test]
+fn test_damaged_frame_is_retransmitted() {
+    let input = [
+        &b"DAY 6\n"[..],
+        &std::fs::read("../day_6/inputs/test.txt").unwrap(),
+    ]
+    .concat();
+    let mut damaged = frame::encode(Kind::Data, 0, &input);
+    damaged[20] ^= 0x01;
+    let serial = MockSerial::new()
+        .bytes(&damaged)
+        .bytes(&frame::encode(Kind::Data, 0, &input))
+        .bytes(&frame::encode(Kind::End, 1, &[]));
+    let mut serial = Framed::new(serial);
+
+    assert_eq!(
+        run(&mut serial),
+        expected("Day 6: Guard Gallivant", &[(1, 41), (2, 6)])
+    );
+    assert_eq!(serial.inner().written_str(), "<NAK 0>\n<ACK 0>\n<ACK 1>\n");
+}
@@ -0,0 +1,70 @@
+#![deny(clippy::mem_forget)] // core::mem::forget is dangerous on ESP32
+#![no_std]
+#![no_main]
+use aoc_2024_days::DAYS;
+use aoc_common::frame::Framed;
+use aoc_common::io;
+use aoc_common::memory::Stack;
+use aoc_common::runner::{self, Meters};
+use aoc_common::timing::Clock;
+use esp_backtrace as _;
+use esp_hal::timer::systimer::SystemTimer;
+use esp_hal::usb_serial_jtag::UsbSerialJtag;
+use esp_hal::{delay::Delay, prelude::*};
+use esp_println::Printer;
+
+/// Times the runs with the system timer, which keeps counting while we wait for input
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTimer::now()
+    }
+
+    fn frequency(&self) -> u64 {
+        SystemTimer::ticks_per_second()
+    }
+}
+
+extern "C" {
+    // The stack's bounds, from the linker script. It grows down from the start to the end.
+    static _stack_start: u8;
+    static _stack_end: u8;
+}
+
+#[entry]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    let delay = Delay::new();
+    let mut usb_serial = Framed::new(UsbSerialJtag::new(peripherals.USB_DEVICE));
+    let mut reader = io::LineReader::<{ runner::LINE_LENGTH }>::new();
+    // SAFETY: The linker script reserves this range for the stack, and we only have one core
+    let stack = unsafe {
+        Stack::new(
+            core::ptr::addr_of!(_stack_start) as usize,
+            core::ptr::addr_of!(_stack_end) as usize,
+        )
+    };
+    let meters = Meters {
+        clock: Some(&SystemClock),
+        stack: Some(&stack),
+    };
+
+    loop {
+        // Every run starts with a header like `DAY 13 PART 2`, followed by the input.
+        // The header may tune the day too, like `DAY 14 width=11 height=7`.
+        // The host uploads it in frames and waits for each to be acknowledged,
+        // so it doesn't overrun our receive buffer.
+        let served = runner::serve(
+            DAYS,
+            &mut usb_serial,
+            &mut reader,
+            &mut Printer,
+            meters,
+        );
+        if !served {
+            delay.delay(100.millis());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Reads the expected answers in `day_N/inputs/answers.toml`.
+//!
+//! Only the bit of TOML these files need is supported: a table per input, named after
+//! its file without the extension, with `part1` and/or `part2` as an integer or string.
+//! An input that needs other values for the day's tunables than their defaults, like an
+//! example with a smaller grid, gives them in `params`.
+//!
+//! ```toml
+//! [test1]
+//! params = "width=11 height=7"
+//! part1 = 480
+//! part2 = "4,6,3"
+//! ```
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_common::params::Values;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// The expected answers for one input, None for the parts that aren't checked
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// The values to run the input with
+    pub params: Values,
+}
+
+impl Expected {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+
+    /// The part to ask for: just the one with an answer, or both.
+    /// Part 2 isn't run without an answer, as it may not handle every input.
+    pub fn wanted_part(&self) -> Option<u8> {
+        match (&self.part1, &self.part2) {
+            (Some(_), None) => Some(1),
+            (None, Some(_)) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+/// Loads a day's manifest, keyed by input name. A day without one expects nothing.
+pub fn load(dir: &Path) -> Result<BTreeMap<String, Expected>> {
+    let path = dir.join("answers.toml");
+    match fs::read_to_string(&path) {
+        Ok(text) => parse(&text).with_context(|| format!("Invalid {}", path.display())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e).with_context(|| format!("Can't read {}", path.display())),
+    }
+}
+
+fn parse(text: &str) -> Result<BTreeMap<String, Expected>> {
+    let mut inputs: BTreeMap<String, Expected> = BTreeMap::new();
+    let mut current = None;
+
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let context = || format!("Line {}: {line}", idx + 1);
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if inputs
+                .insert(name.to_owned(), Expected::default())
+                .is_some()
+            {
+                bail!("{}: duplicate input", context());
+            }
+            current = Some(name);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("{}: expected `[input]` or `key = value`", context());
+        };
+        let Some(expected) = current.and_then(|name| inputs.get_mut(name)) else {
+            bail!("{}: answer outside of an `[input]`", context());
+        };
+        let value = parse_value(value).with_context(context)?;
+        match key.trim() {
+            "part1" => expected.part1 = Some(value),
+            "part2" => expected.part2 = Some(value),
+            "params" => {
+                for word in value.split_ascii_whitespace() {
+                    expected
+                        .params
+                        .set_word(word)
+                        .map_err(|e| anyhow!("{}: {e}", context()))?;
+                }
+            }
+            _ => bail!("{}: expected part1, part2 or params", context()),
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Parses an integer or a string without escapes, followed by an optional comment
+fn parse_value(value: &str) -> Result<String> {
+    let value = value.trim();
+    if let Some(rest) = value.strip_prefix('"') {
+        let (s, rest) = rest.split_once('"').context("Unterminated string")?;
+        if !is_comment(rest) {
+            bail!("Unexpected text after the string");
+        }
+        return Ok(s.to_owned());
+    }
+
+    let value = value.split('#').next().unwrap_or_default().trim();
+    let n: u64 = value.parse().context("Expected an integer or a string")?;
+    Ok(n.to_string())
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let inputs =
+            parse("# Comment\n[test1]\npart1 = 480 # Tokens\n\n[test2]\npart2 = \"4,6 # 3\"\n")
+                .unwrap();
+        assert_eq!(inputs["test1"].part(1), Some("480"));
+        assert_eq!(inputs["test1"].part(2), None);
+        assert_eq!(inputs["test2"].part(2), Some("4,6 # 3"));
+        assert!(inputs["test2"].params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let inputs = parse("[test1]\nparams = \"width=11 height=7\"\npart1 = 12\n").unwrap();
+        assert_eq!(inputs["test1"].params.get("width"), Some(11));
+        assert_eq!(inputs["test1"].params.get("height"), Some(7));
+        assert_eq!(inputs["test1"].wanted_part(), Some(1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("part1 = 1").is_err());
+        assert!(parse("[a]\npart3 = 1").is_err());
+        assert!(parse("[a]\npart1 = x").is_err());
+        assert!(parse("[a]\n[a]").is_err());
+        assert!(parse("[a]\npart1 = \"open").is_err());
+        assert!(parse("[a]\nparams = \"width\"").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Times every day's inputs and compares the times with a stored baseline
+use crate::answers;
+use crate::local;
+use crate::monitor::{Monitor, Outcome};
+use crate::record::{Record, Timing};
+use anyhow::{bail, Context, Result};
+use aoc_common::runner::Header;
+use aoc_common::timing::to_micros;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where the baselines are kept, one per target
+const BASELINE_DIR: &str = "bench";
+/// Anything that changes less than this is noise
+const MIN_REGRESSION_US: u64 = 500;
+const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Where the days run
+pub enum Target {
+    Host,
+    /// The device or its emulation, with how long to wait for each input's answers
+    Device {
+        name: &'static str,
+        monitor: Monitor,
+        timeout: Duration,
+    },
+}
+
+impl Target {
+    fn name(&self) -> &'static str {
+        match self {
+            Target::Host => "host",
+            Target::Device { name, .. } => name,
+        }
+    }
+
+    /// Runs an input and returns the output lines, None if the run failed
+    fn run(&mut self, header: Header, input: &Path) -> Result<Option<Vec<String>>> {
+        match self {
+            Target::Host => Ok(local::solve(header, input)
+                .ok()
+                .map(|out| out.lines().map(str::to_owned).collect())),
+            Target::Device {
+                monitor, timeout, ..
+            } => {
+                let outcome = monitor.run(header, input, *timeout)?;
+                let output = monitor.take_output();
+                Ok((outcome == Outcome::Done).then_some(output))
+            }
+        }
+    }
+}
+
+/// The times of one input's steps in microseconds, indexed like [`STEPS`]
+type Times = [Option<u64>; 3];
+
+pub fn run(day: Option<u8>, mut target: Target, save: bool, tolerance: u64) -> Result<()> {
+    let path = PathBuf::from(BASELINE_DIR).join(format!("{}.json", target.name()));
+    let baseline = load(&path)?;
+    if baseline.is_empty() && !save {
+        println!(
+            "# No baseline in {} yet, save one with --save",
+            path.display()
+        );
+    }
+
+    let mut results = Map::new();
+    let mut table = vec![row("input", STEPS.map(str::to_owned))];
+    let mut regressions = Vec::new();
+
+    for day in local::select(day)? {
+        let expected = answers::load(&local::inputs_dir(day.number))?;
+        for input in local::default_inputs(day.number)? {
+            let name = input.file_stem().unwrap_or_default().to_string_lossy();
+            let key = format!("{}/{name}", day.number);
+            let entry = expected.get(&*name);
+            let header = Header {
+                day: day.number,
+                part: entry.and_then(|e| e.wanted_part()),
+                params: entry.map(|e| e.params.clone()).unwrap_or_default(),
+            };
+
+            let Some(output) = target.run(header, &input)? else {
+                table.push(row(
+                    &input.display().to_string(),
+                    STEPS.map(|_| "failed".into()),
+                ));
+                continue;
+            };
+            let times = times(&output);
+
+            let mut cells = STEPS.map(|_| String::new());
+            for (i, step) in STEPS.iter().enumerate() {
+                let Some(us) = times[i] else {
+                    continue;
+                };
+                cells[i] = format!("{:.3} ms", us as f64 / 1000.0);
+
+                let before = baseline.get(&key).and_then(|b| b[step].as_u64());
+                if let Some(before) = before.filter(|&b| is_regression(b, us, tolerance)) {
+                    cells[i] += " !";
+                    regressions.push(format!(
+                        "{key} {step}: {:.3} ms -> {:.3} ms",
+                        before as f64 / 1000.0,
+                        us as f64 / 1000.0
+                    ));
+                }
+            }
+            table.push(row(&input.display().to_string(), cells));
+            results.insert(
+                key,
+                json!({"parse": times[0], "part1": times[1], "part2": times[2]}),
+            );
+        }
+    }
+
+    println!();
+    for line in table {
+        println!("{line}");
+    }
+
+    if save {
+        fs::create_dir_all(BASELINE_DIR)?;
+        fs::write(&path, serde_json::to_string_pretty(&results)? + "\n")
+            .with_context(|| format!("Can't write {}", path.display()))?;
+        println!("# Saved the baseline to {}", path.display());
+    } else if !regressions.is_empty() {
+        println!("\n# Slower than the baseline by more than {tolerance}%:");
+        for regression in &regressions {
+            println!("{regression}");
+        }
+        bail!("{} step(s) regressed", regressions.len());
+    }
+    Ok(())
+}
+
+/// Collects the times of a run from its timing and answer records
+fn times(output: &[String]) -> Times {
+    let mut times = [None; 3];
+    let Some(timing) = output.iter().find_map(|line| Timing::parse(line)) else {
+        return times;
+    };
+
+    times[0] = Some(to_micros(timing.parse_cycles, timing.hz));
+    for record in output.iter().filter_map(|line| Record::parse(line)) {
+        if let (Some(cycles), 1 | 2) = (record.cycles, record.part) {
+            times[record.part as usize] = Some(to_micros(cycles, timing.hz));
+        }
+    }
+    times
+}
+
+fn is_regression(before: u64, after: u64, tolerance: u64) -> bool {
+    after > before + MIN_REGRESSION_US && after * 100 > before * (100 + tolerance)
+}
+
+fn row(input: &str, cells: [String; 3]) -> String {
+    format!(
+        "{input:<32} {:>14} {:>14} {:>14}",
+        cells[0], cells[1], cells[2]
+    )
+}
+
+fn load(path: &Path) -> Result<Map<String, Value>> {
+    match fs::read_to_string(path) {
+        Ok(text) => {
+            serde_json::from_str(&text).with_context(|| format!("Invalid {}", path.display()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(e).with_context(|| format!("Can't read {}", path.display())),
+    }
+}
@@ -0,0 +1,80 @@
+//! Runs days on the host and compares their answers with `day_N/inputs/answers.toml`
+use crate::answers::{self, Expected};
+use crate::local;
+use crate::record::Record;
+use anyhow::{bail, Result};
+use aoc_common::runner::Header;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+pub fn run(day: Option<u8>) -> Result<()> {
+    let mut regressed = BTreeSet::new();
+
+    for day in local::select(day)? {
+        let dir = local::inputs_dir(day.number);
+        let mut expected = answers::load(&dir)?;
+
+        for input in local::default_inputs(day.number)? {
+            let name = input.file_stem().unwrap_or_default().to_string_lossy();
+            let Some(expected) = expected.remove(&*name) else {
+                println!("{:<32} no answers", input.display());
+                continue;
+            };
+
+            let failures = check(day.number, &input, &expected);
+            if failures.is_empty() {
+                println!("{:<32} ok", input.display());
+            }
+            for failure in failures {
+                println!("{:<32} FAIL {failure}", input.display());
+                regressed.insert(day.number);
+            }
+        }
+
+        // Answers for inputs that have gone missing
+        for name in expected.keys() {
+            let input = dir.join(format!("{name}.txt"));
+            println!("{:<32} FAIL no such input", input.display());
+            regressed.insert(day.number);
+        }
+    }
+
+    if !regressed.is_empty() {
+        let days: Vec<String> = regressed.iter().map(u8::to_string).collect();
+        bail!("Regressed days: {}", days.join(", "));
+    }
+    Ok(())
+}
+
+/// Runs one input, returns what didn't match
+fn check(day: u8, input: &Path, expected: &Expected) -> Vec<String> {
+    let part = expected.wanted_part();
+    let header = Header {
+        day,
+        part,
+        params: expected.params.clone(),
+    };
+    let output = match local::solve(header, input) {
+        Ok(output) => output,
+        Err(e) => return vec![format!("{e:#}")],
+    };
+    let records: Vec<Record> = output.lines().filter_map(Record::parse).collect();
+
+    let mut failures = Vec::new();
+    for part in [1, 2] {
+        let Some(want) = expected.part(part) else {
+            continue;
+        };
+        let got = records
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.value.as_deref());
+        if got != Some(want) {
+            failures.push(format!(
+                "part {part}: expected {want}, got {}",
+                got.unwrap_or("unsolved")
+            ));
+        }
+    }
+    failures
+}
@@ -0,0 +1,62 @@
+//! Builds and flashes the firmware
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const FIRMWARE_DIR: &str = "firmware";
+const PACKAGE: &str = "aoc-2024-firmware";
+const TARGET: &str = "riscv32imc-unknown-none-elf";
+
+/// Builds the firmware and returns the path to the binary
+pub fn build(profile: &str) -> Result<PathBuf> {
+    println!("# Compiling");
+    let status = Command::new("cargo")
+        .args(["build", "--profile", profile])
+        .current_dir(FIRMWARE_DIR)
+        .status()
+        .context("Can't run cargo")?;
+    if !status.success() {
+        bail!("Building the firmware failed");
+    }
+
+    // Cargo names the dev profile's directory after its old name
+    let profile_dir = match profile {
+        "dev" => "debug",
+        p => p,
+    };
+    Ok(target_directory()?
+        .join(TARGET)
+        .join(profile_dir)
+        .join(PACKAGE))
+}
+
+pub fn flash(firmware: &Path, port: &str) -> Result<()> {
+    println!("# Flashing {}", firmware.display());
+    let status = Command::new("espflash")
+        .args(["flash", "--port", port])
+        .arg(firmware)
+        .status()
+        .context("Can't run espflash, see `make devsetup`")?;
+    if !status.success() {
+        bail!("Flashing the firmware failed");
+    }
+    Ok(())
+}
+
+/// The firmware is its own workspace, so it has its own target directory
+fn target_directory() -> Result<PathBuf> {
+    let out = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .current_dir(FIRMWARE_DIR)
+        .output()
+        .context("Can't run cargo")?;
+    if !out.status.success() {
+        bail!("cargo metadata failed");
+    }
+
+    let meta: serde_json::Value = serde_json::from_slice(&out.stdout)?;
+    meta["target_directory"]
+        .as_str()
+        .map(PathBuf::from)
+        .context("cargo metadata has no target_directory")
+}
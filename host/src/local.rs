@@ -0,0 +1,107 @@
+//! Runs the no_std solutions on the host, through the same header and line reader
+//! the firmware uses.
+use crate::meters::{self, InstantClock, STACK_SIZE};
+use crate::record;
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_2024_days::DAYS;
+use aoc_common::io::LineReader;
+use aoc_common::params::Values;
+use aoc_common::registry::{self, Day};
+use aoc_common::runner::{self, Header, Meters, LINE_LENGTH};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+pub fn run(
+    day: Option<u8>,
+    inputs: &[PathBuf],
+    part: Option<u8>,
+    params: &Values,
+    json: bool,
+) -> Result<()> {
+    let mut failed = 0;
+    for day in select(day)? {
+        let inputs = if inputs.is_empty() {
+            default_inputs(day.number)?
+        } else {
+            inputs.to_vec()
+        };
+
+        for input in inputs {
+            record::print(&format!("# {}", input.display()), json);
+            let header = Header {
+                day: day.number,
+                part,
+                params: params.clone(),
+            };
+            match solve(header, &input) {
+                Ok(output) => output.lines().for_each(|line| record::print(line, json)),
+                Err(e) => {
+                    record::print(&format!("{e:#}"), json);
+                    failed += 1;
+                }
+            }
+            if !json {
+                println!();
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} run(s) failed");
+    }
+    Ok(())
+}
+
+/// The given day, or every day if None
+pub fn select(day: Option<u8>) -> Result<Vec<&'static Day>> {
+    match day {
+        Some(number) => {
+            Ok(vec![registry::find(DAYS, number)
+                .ok_or_else(|| anyhow!("Day {number} has no solution"))?])
+        }
+        None => Ok(DAYS.iter().collect()),
+    }
+}
+
+pub fn inputs_dir(day: u8) -> PathBuf {
+    PathBuf::from(format!("day_{day}/inputs"))
+}
+
+/// Lists `day_N/inputs/*.txt`
+pub fn default_inputs(day: u8) -> Result<Vec<PathBuf>> {
+    let dir = inputs_dir(day);
+    let mut inputs: Vec<PathBuf> = fs::read_dir(&dir)
+        .with_context(|| format!("Can't list {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Sends the header and input through the runner, like the device would receive them
+pub fn solve(header: Header, input: &Path) -> Result<String> {
+    let mut data = format!("{header}\n").into_bytes();
+    data.extend(fs::read(input).with_context(|| format!("Can't read {}", input.display()))?);
+
+    let solver = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let mut reader = data.as_slice();
+            let mut lines = LineReader::<LINE_LENGTH>::new();
+            let mut out = String::new();
+            let stack = meters::thread_stack();
+            let clock = InstantClock::default();
+            let meters = Meters {
+                clock: Some(&clock),
+                stack: Some(&stack),
+            };
+            runner::run(DAYS, &mut reader, &mut lines, &mut out, meters).map(|_| out)
+        })?;
+
+    match solver.join() {
+        Ok(result) => result.map_err(|e| anyhow!("{e}")),
+        Err(_) => bail!("Day {} panicked", header.day),
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{anyhow, Result};
+use aoc_common::params::Values;
+use aoc_common::runner::Header;
+use clap::{Args, Parser, Subcommand};
+use serialport::SerialPort;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+mod answers;
+mod bench;
+mod check;
+mod firmware;
+mod local;
+mod meters;
+mod monitor;
+mod record;
+mod serial;
+
+/// Runs the Advent of Code 2024 solutions
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs days on the host instead of the device
+    Run {
+        /// The day to run, or every day if omitted
+        day: Option<u8>,
+        /// Input files, defaults to every `day_N/inputs/*.txt`
+        #[arg(requires = "day")]
+        inputs: Vec<PathBuf>,
+        /// Only report this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Tunes the day, see `params`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+        /// Print the answers as JSON Lines, and everything else to stderr
+        #[arg(long)]
+        json: bool,
+    },
+    /// Lists the tunables each day declares, which `--param` can set
+    Params {
+        /// The day to list, or every day if omitted
+        day: Option<u8>,
+    },
+    /// Runs days on the host and compares their answers with `day_N/inputs/answers.toml`.
+    ///
+    /// Fails with the days that regressed.
+    Check {
+        /// The day to check, or every day if omitted
+        day: Option<u8>,
+    },
+    /// Times every day's inputs and compares the times with `bench/<target>.json`.
+    ///
+    /// Runs on the host, unless `--device` or `--pty` is given.
+    /// Fails with the steps that got slower than the baseline.
+    Bench {
+        /// The day to time, or every day if omitted
+        day: Option<u8>,
+        /// Run on the device instead of the host
+        #[arg(long)]
+        device: bool,
+        #[command(flatten)]
+        connection: Connection,
+        /// Store the times as the new baseline
+        #[arg(long)]
+        save: bool,
+        /// How many percent slower than the baseline a step may get
+        #[arg(long, default_value_t = 10)]
+        tolerance: u64,
+    },
+    /// Builds the firmware
+    Build {
+        #[arg(long, default_value = "release")]
+        profile: String,
+    },
+    /// Builds and flashes the firmware
+    Flash {
+        #[arg(long, default_value = "release")]
+        profile: String,
+        /// Serial port of the device, found automatically if omitted
+        #[arg(long, env = "AOC_PORT")]
+        port: Option<String>,
+    },
+    /// Sends an input to the device and prints its answers.
+    ///
+    /// Exits with 2 if the device reports an error and 3 if it panics.
+    Monitor(Session),
+    /// Builds and flashes the firmware, then runs `monitor`
+    Device {
+        #[arg(long, default_value = "release")]
+        profile: String,
+        /// Skip building and flashing, the firmware is already on the device
+        #[arg(long)]
+        no_flash: bool,
+        #[command(flatten)]
+        session: Session,
+    },
+}
+
+#[derive(Args)]
+struct Session {
+    day: u8,
+    input: PathBuf,
+    /// Only report this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Tunes the day, see `params`
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+    #[command(flatten)]
+    connection: Connection,
+    /// Print the answers as JSON Lines, and everything else to stderr
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct Connection {
+    /// Serial port of the device, found automatically if omitted
+    #[arg(long, env = "AOC_PORT")]
+    port: Option<String>,
+    /// Talk to the firmware's main loop running on the host over a pseudo terminal,
+    /// instead of a real device
+    #[arg(long, conflicts_with = "port")]
+    pty: bool,
+    /// Seconds to wait for the device to acknowledge a frame before sending it again
+    #[arg(long, default_value_t = 5)]
+    ack_timeout: u64,
+    /// Seconds to wait for the answers once the input is sent
+    #[arg(long, default_value_t = 600)]
+    timeout: u64,
+}
+
+impl Connection {
+    fn open(&self) -> Result<Box<dyn SerialPort>> {
+        if self.pty {
+            return serial::loopback();
+        }
+        match &self.port {
+            Some(port) => serial::open(port),
+            None => serial::open(&serial::find_port()?),
+        }
+    }
+
+    fn monitor(&self, json: bool) -> Result<monitor::Monitor> {
+        monitor::Monitor::new(self.open()?, Duration::from_secs(self.ack_timeout), json)
+    }
+}
+
+impl Session {
+    fn run(&self) -> Result<ExitCode> {
+        let header = Header {
+            day: self.day,
+            part: self.part,
+            params: values(&self.params)?,
+        };
+        let mut monitor = self.connection.monitor(self.json)?;
+        let timeout = Duration::from_secs(self.connection.timeout);
+        let outcome = monitor.run(header, &self.input, timeout)?;
+        Ok(outcome.exit_code())
+    }
+}
+
+/// The values given with `--param`
+fn values(params: &[String]) -> Result<Values> {
+    let mut values = Values::new();
+    for param in params {
+        values
+            .set_word(param)
+            .map_err(|e| anyhow!("--param {param}: {e}"))?;
+    }
+    Ok(values)
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            inputs,
+            part,
+            params,
+            json,
+        } => local::run(day, &inputs, part, &values(&params)?, json)?,
+        Command::Params { day } => {
+            for day in local::select(day)? {
+                println!("Day {}: {}", day.number, day.title);
+                for param in day.params() {
+                    println!(
+                        "  {}={:<8} {} ({}..={})",
+                        param.name, param.default, param.help, param.min, param.max
+                    );
+                }
+            }
+        }
+        Command::Check { day } => check::run(day)?,
+        Command::Bench {
+            day,
+            device,
+            connection,
+            save,
+            tolerance,
+        } => {
+            let target = if device || connection.pty {
+                bench::Target::Device {
+                    name: if connection.pty { "pty" } else { "device" },
+                    monitor: connection.monitor(false)?,
+                    timeout: Duration::from_secs(connection.timeout),
+                }
+            } else {
+                bench::Target::Host
+            };
+            bench::run(day, target, save, tolerance)?;
+        }
+        Command::Build { profile } => {
+            firmware::build(&profile)?;
+        }
+        Command::Flash { profile, port } => {
+            let port = port.map_or_else(serial::find_port, Ok)?;
+            firmware::flash(&firmware::build(&profile)?, &port)?;
+        }
+        Command::Monitor(session) => return session.run(),
+        Command::Device {
+            profile,
+            no_flash,
+            session,
+        } => {
+            // There's nothing to flash when talking to the emulated device
+            if !no_flash && !session.connection.pty {
+                let port = session
+                    .connection
+                    .port
+                    .clone()
+                    .map_or_else(serial::find_port, Ok)?;
+                firmware::flash(&firmware::build(&profile)?, &port)?;
+            }
+            return session.run();
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,37 @@
+//! Measures runs on the host, like the firmware does on the device
+use aoc_common::memory::{self, Stack};
+use aoc_common::timing::Clock;
+use std::time::Instant;
+
+/// Days keep all of their state on the stack, just like on the device
+pub const STACK_SIZE: usize = 64 * 1024 * 1024;
+/// How much of the stack is painted, painting all of it for every step takes too long
+const PAINTED: usize = 8 * 1024 * 1024;
+
+/// Counts nanoseconds since it was created
+pub struct InstantClock(Instant);
+
+impl Default for InstantClock {
+    fn default() -> Self {
+        Self(Instant::now())
+    }
+}
+
+impl Clock for InstantClock {
+    fn now(&self) -> u64 {
+        self.0.elapsed().as_nanos() as u64
+    }
+
+    fn frequency(&self) -> u64 {
+        1_000_000_000
+    }
+}
+
+/// The stack of a thread spawned with [`STACK_SIZE`], from the caller's frame down.
+/// Must be called at the start of the thread.
+pub fn thread_stack() -> Stack {
+    let top = memory::stack_pointer();
+    // SAFETY: The thread's stack reaches STACK_SIZE below its start, we stay well
+    // clear of its end
+    unsafe { Stack::new(top, top - PAINTED) }
+}
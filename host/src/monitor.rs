@@ -0,0 +1,212 @@
+//! Sends an input to the device and follows its output
+use crate::record;
+use anyhow::{bail, Context, Result};
+use aoc_common::frame::{self, Kind, Reply, MAX_PAYLOAD};
+use aoc_common::runner::{Header, EOT, ERR};
+use serialport::SerialPort;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a frame is sent before giving up on the device
+const MAX_ATTEMPTS: usize = 5;
+/// How long to keep printing after a panic, to catch the whole backtrace
+const PANIC_GRACE: Duration = Duration::from_millis(500);
+/// Boot messages and the like, sent before we start
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// How a run on the device ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Done,
+    /// The device couldn't handle the input, e.g. an unknown day
+    Error,
+    Panic,
+}
+
+impl Outcome {
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            Outcome::Done => ExitCode::SUCCESS,
+            Outcome::Error => ExitCode::from(2),
+            Outcome::Panic => ExitCode::from(3),
+        }
+    }
+}
+
+pub struct Monitor {
+    port: Box<dyn SerialPort>,
+    lines: Receiver<String>,
+    ack_timeout: Duration,
+    /// Print the answers as JSON Lines, and everything else to stderr
+    json: bool,
+    /// What the device printed during the runs, besides the protocol
+    output: Vec<String>,
+}
+
+impl Monitor {
+    pub fn new(port: Box<dyn SerialPort>, ack_timeout: Duration, json: bool) -> Result<Self> {
+        let reader = port.try_clone().context("Can't clone the serial port")?;
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || read_lines(reader, tx));
+
+        Ok(Self {
+            port,
+            lines,
+            ack_timeout,
+            json,
+            output: Vec::new(),
+        })
+    }
+
+    /// Uploads the header and input in frames, waiting for the device to acknowledge
+    /// each one, then waits for the device to finish
+    pub fn run(&mut self, header: Header, input: &Path, timeout: Duration) -> Result<Outcome> {
+        let input =
+            std::fs::read(input).with_context(|| format!("Can't read {}", input.display()))?;
+
+        // Show whatever the device printed before we got here
+        while let Ok(line) = self.lines.recv_timeout(SETTLE_TIME) {
+            record::print(&line, self.json);
+        }
+
+        record::print(&format!("# Sending {header}"), self.json);
+        let mut data = format!("{header}\n").into_bytes();
+        data.extend(input);
+
+        let mut seq = 0u8;
+        for payload in data.chunks(MAX_PAYLOAD) {
+            if let Some(outcome) = self.send_frame(Kind::Data, seq, payload)? {
+                return Ok(outcome);
+            }
+            seq = seq.wrapping_add(1);
+        }
+        if let Some(outcome) = self.send_frame(Kind::End, seq, &[])? {
+            return Ok(outcome);
+        }
+
+        self.wait_for_eot(timeout)
+    }
+
+    /// Sends a frame until the device acknowledges it
+    fn send_frame(&mut self, kind: Kind, seq: u8, payload: &[u8]) -> Result<Option<Outcome>> {
+        let frame = frame::encode(kind, seq, payload);
+
+        for _ in 0..MAX_ATTEMPTS {
+            self.port.write_all(&frame)?;
+            self.port.flush()?;
+
+            let deadline = Instant::now() + self.ack_timeout;
+            loop {
+                let line = match self.recv_until(deadline) {
+                    Ok(line) => line,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => bail!("The device disconnected"),
+                };
+
+                match Reply::parse(&line) {
+                    Some(Reply::Ack(acked)) if acked == seq => return Ok(None),
+                    // The frame got damaged on the way
+                    Some(Reply::Nak(_)) => break,
+                    // A late answer to a frame we sent again
+                    Some(Reply::Ack(_)) => {}
+                    None => {
+                        if let Some(outcome) = self.handle(&line) {
+                            return Ok(Some(outcome));
+                        }
+                    }
+                }
+            }
+        }
+
+        bail!("The device didn't acknowledge frame {seq} after {MAX_ATTEMPTS} attempts")
+    }
+
+    fn wait_for_eot(&mut self, timeout: Duration) -> Result<Outcome> {
+        let deadline = Instant::now() + timeout;
+        let mut outcome = Outcome::Done;
+
+        loop {
+            let line = match self.recv_until(deadline) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    bail!("The device didn't finish within {:?}", timeout)
+                }
+                Err(RecvTimeoutError::Disconnected) => bail!("The device disconnected"),
+            };
+
+            if line == EOT {
+                return Ok(outcome);
+            }
+            match self.handle(&line) {
+                Some(Outcome::Error) => outcome = Outcome::Error,
+                Some(o) => return Ok(o),
+                None => {}
+            }
+        }
+    }
+
+    /// Prints a line of device output, returning the outcome it implies if any
+    fn handle(&mut self, line: &str) -> Option<Outcome> {
+        if Reply::parse(line).is_some() {
+            return None;
+        }
+        if let Some(msg) = line.strip_prefix(ERR) {
+            eprintln!("Device error:{msg}");
+            return Some(Outcome::Error);
+        }
+
+        record::print(line, self.json);
+        self.output.push(line.to_owned());
+        if is_panic(line) {
+            // Print the rest of the backtrace
+            while let Ok(line) = self.lines.recv_timeout(PANIC_GRACE) {
+                record::print(&line, self.json);
+            }
+            return Some(Outcome::Panic);
+        }
+        None
+    }
+
+    /// Takes what the device printed since the last call
+    pub fn take_output(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.output)
+    }
+
+    fn recv_until(&self, deadline: Instant) -> Result<String, RecvTimeoutError> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        self.lines.recv_timeout(timeout)
+    }
+}
+
+/// Recognizes the start of esp-backtrace's panic and exception output
+fn is_panic(line: &str) -> bool {
+    (line.starts_with("====") && line.contains("PANIC"))
+        || line.starts_with("!! A panic occured")
+        || line.starts_with("Exception occurred")
+}
+
+fn read_lines(port: Box<dyn SerialPort>, tx: mpsc::Sender<String>) {
+    let mut reader = BufReader::new(port);
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) => return,
+            Ok(_) if buf.ends_with(b"\n") => {
+                let line = String::from_utf8_lossy(&buf);
+                if tx.send(line.trim_end().to_owned()).is_err() {
+                    return;
+                }
+                buf.clear();
+            }
+            // Partial line, the rest is still on its way
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => {}
+            Err(_) => return,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Reads the answer records in the runner's output, see `aoc_common::output`
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// None if the part is unsolved
+    pub value: Option<String>,
+    pub cycles: Option<u64>,
+    pub peak_mem: Option<u64>,
+}
+
+impl Record {
+    /// Parses a line of output, returns None if it isn't a record
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json: Value = serde_json::from_str(line).ok()?;
+        let value = match &json["value"] {
+            Value::Null => None,
+            Value::Number(n) => Some(n.to_string()),
+            Value::String(s) => Some(s.clone()),
+            _ => return None,
+        };
+
+        Some(Self {
+            day: json["day"].as_u64()?.try_into().ok()?,
+            part: json["part"].as_u64()?.try_into().ok()?,
+            value,
+            cycles: json["cycles"].as_u64(),
+            peak_mem: json["peak_mem"].as_u64(),
+        })
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Part {}: {}",
+            self.part,
+            self.value.as_deref().unwrap_or("unsolved")
+        )?;
+
+        let measured: Vec<String> = [
+            self.cycles.map(|c| format!("{c} cycles")),
+            self.peak_mem.map(|m| format!("{m} B peak")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !measured.is_empty() {
+            write!(f, " ({})", measured.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// How a run was timed, see `aoc_common::output::Timing`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u8,
+    pub hz: u64,
+    pub parse_cycles: u64,
+}
+
+impl Timing {
+    /// Parses a line of output, returns None if it isn't a timing record
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json: Value = serde_json::from_str(line).ok()?;
+        Some(Self {
+            day: json["day"].as_u64()?.try_into().ok()?,
+            hz: json["hz"].as_u64()?,
+            parse_cycles: json["parse_cycles"].as_u64()?,
+        })
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse: {} cycles at {} Hz", self.parse_cycles, self.hz)
+    }
+}
+
+/// How full a container got, see `aoc_common::output::UsageRecord`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+    pub day: u8,
+    pub name: String,
+    pub peak: u64,
+    pub capacity: u64,
+}
+
+impl Usage {
+    /// Parses a line of output, returns None if it isn't a usage record
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json: Value = serde_json::from_str(line).ok()?;
+        Some(Self {
+            day: json["day"].as_u64()?.try_into().ok()?,
+            name: json["usage"].as_str()?.to_owned(),
+            peak: json["peak"].as_u64()?,
+            capacity: json["capacity"].as_u64()?,
+        })
+    }
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = self.peak * 100 / self.capacity.max(1);
+        write!(
+            f,
+            "Usage of {}: {} / {} ({percent}%)",
+            self.name, self.peak, self.capacity
+        )
+    }
+}
+
+/// Prints a line of the runner's output, records as readable text.
+/// With `json`, only the records go to stdout, as they are, and the rest to stderr.
+pub fn print(line: &str, json: bool) {
+    if json {
+        let is_record = Record::parse(line).is_some()
+            || Timing::parse(line).is_some()
+            || Usage::parse(line).is_some();
+        match is_record {
+            true => println!("{line}"),
+            false => eprintln!("{line}"),
+        }
+    } else if let Some(record) = Record::parse(line) {
+        println!("{record}");
+    } else if let Some(timing) = Timing::parse(line) {
+        println!("{timing}");
+    } else if let Some(usage) = Usage::parse(line) {
+        println!("{usage}");
+    } else {
+        println!("{line}");
+    }
+}
@@ -0,0 +1,133 @@
+//! Finds and opens the device's serial port, or emulates a device on a pseudo terminal
+use crate::meters::{self, InstantClock, STACK_SIZE};
+use anyhow::{bail, Context, Result};
+use aoc_2024_days::DAYS;
+use aoc_common::frame::Framed;
+use aoc_common::io::LineReader;
+use aoc_common::runner::{self, Meters, LINE_LENGTH};
+use serialport::{SerialPort, SerialPortType, TTYPort};
+use std::fmt::{self, Write as _};
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+
+/// The USB vendor ID of the C3's built-in USB serial/JTAG controller
+const ESPRESSIF_VID: u16 = 0x303a;
+const BAUD_RATE: u32 = 115_200;
+/// How long a single read waits, the caller decides how long to keep trying
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Finds the serial port of the first connected ESP32-C3
+pub fn find_port() -> Result<String> {
+    let ports = serialport::available_ports().context("Can't list serial ports")?;
+    let device = ports.iter().find(|p| match &p.port_type {
+        SerialPortType::UsbPort(usb) => usb.vid == ESPRESSIF_VID,
+        _ => false,
+    });
+
+    match device {
+        Some(port) => Ok(port.port_name.clone()),
+        None => {
+            let names: Vec<&str> = ports.iter().map(|p| p.port_name.as_str()).collect();
+            bail!(
+                "No ESP32-C3 found, pick one of [{}] with --port",
+                names.join(", ")
+            )
+        }
+    }
+}
+
+pub fn open(port: &str) -> Result<Box<dyn SerialPort>> {
+    serialport::new(port, BAUD_RATE)
+        .timeout(READ_TIMEOUT)
+        .open()
+        .with_context(|| format!("Can't open {port}"))
+}
+
+/// Opens a pseudo terminal with the firmware's main loop running on the other end
+pub fn loopback() -> Result<Box<dyn SerialPort>> {
+    let (mut host, device) = TTYPort::pair().context("Can't open a pseudo terminal")?;
+    host.set_timeout(READ_TIMEOUT)?;
+
+    let out = device.try_clone_native()?;
+    thread::Builder::new()
+        .name("emulated device".into())
+        .stack_size(STACK_SIZE)
+        .spawn(move || emulate_device(device, out))?;
+
+    Ok(Box::new(host))
+}
+
+fn emulate_device(port: TTYPort, out: TTYPort) {
+    let mut reader = Framed::new(Blocking(port));
+    let mut out = Printer(out);
+    let mut lines = LineReader::<LINE_LENGTH>::new();
+    let stack = meters::thread_stack();
+    let clock = InstantClock::default();
+    let meters = Meters {
+        clock: Some(&clock),
+        stack: Some(&stack),
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| loop {
+        if !runner::serve(DAYS, &mut reader, &mut lines, &mut out, meters) {
+            thread::sleep(Duration::from_millis(100));
+        }
+    }));
+
+    // Report it like esp-backtrace does, then halt like the real device.
+    // Returning would close the terminal, which can swallow the report.
+    if let Err(payload) = result {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        let _ = writeln!(
+            out,
+            "\n====================== PANIC ======================\n{msg}"
+        );
+        loop {
+            thread::park();
+        }
+    }
+}
+
+/// Blocks until data arrives, like the device's USB serial port.
+/// The frame replies are written through it as well.
+struct Blocking(TTYPort);
+
+impl embedded_io::ErrorType for Blocking {
+    type Error = io::Error;
+}
+
+impl embedded_io::Read for Blocking {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        loop {
+            match self.0.read(buf) {
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => continue,
+                res => return res,
+            }
+        }
+    }
+}
+
+impl embedded_io::Write for Blocking {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+/// Writes text to the port, like esp-println's Printer
+struct Printer(TTYPort);
+
+impl fmt::Write for Printer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
@@ -0,0 +1,20 @@
+//! Checks every day against the answers in its `inputs/answers.toml`
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_every_day_matches_its_answers() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc-host"))
+        .arg("check")
+        .current_dir(workspace)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}